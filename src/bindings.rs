@@ -14,6 +14,7 @@ use crate::runtime::JsRuntime;
 use crate::signals;
 use crate::stdio;
 use crate::timers;
+use crate::util;
 use anyhow::Error;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
@@ -35,6 +36,7 @@ lazy_static! {
             ("http_parser", http_parser::initialize),
             ("signals", signals::initialize),
             ("exceptions", exceptions::initialize),
+            ("util", util::initialize),
         ];
         HashMap::from_iter(bindings.into_iter())
     };
@@ -190,7 +192,7 @@ pub fn set_exception_code(
     if let Some(error) = error.downcast_ref::<IoError>() {
         if let Some(code) = extract_error_code(error) {
             let key = v8::String::new(scope, "code").unwrap();
-            let value = v8::String::new(scope, &format!("ERR_{code}")).unwrap();
+            let value = v8::String::new(scope, code).unwrap();
             exception.set(scope, key.into(), value.into());
         }
     }
@@ -1,5 +1,6 @@
 use crate::bundle;
 use crate::compile;
+use crate::coverage;
 use crate::dotenv;
 use crate::errors::generic_error;
 use crate::errors::unwrap_or_exit;
@@ -185,12 +186,14 @@ struct TestArgs {
     )]
     path: Option<PathBuf>,
     #[arg(
-        help = "Stop after the first failure",
-        default_value = "false",
-        action = ArgAction::SetTrue,
+        help = "Stop after the first (or N-th) failure",
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        require_equals = true,
+        num_args = ..=1,
         long
     )]
-    fail_fast: bool,
+    fail_fast: Option<Option<u32>>,
     #[arg(
         help = "Run tests with this regex pattern in test description",
         value_name = "FILTER",
@@ -198,6 +201,57 @@ struct TestArgs {
         long
     )]
     filter: Option<String>,
+    #[arg(
+        help = "Run tests in random order (a random seed is used if omitted)",
+        value_name = "NUMBER",
+        require_equals = true,
+        num_args = ..=1,
+        long
+    )]
+    shuffle: Option<Option<u32>>,
+    #[arg(
+        help = "Run independent async tests concurrently (defaults to the number of CPUs)",
+        value_name = "NUMBER",
+        value_parser = clap::value_parser!(u32).range(1..),
+        require_equals = true,
+        num_args = ..=1,
+        long
+    )]
+    parallel: Option<Option<u32>>,
+    #[arg(
+        help = "Update the stored snapshots of snapshot assertions",
+        default_value = "false",
+        action = ArgAction::SetTrue,
+        long
+    )]
+    update: bool,
+    #[arg(
+        help = "Capture where each async op was started, to report leaked ops",
+        default_value = "false",
+        action = ArgAction::SetTrue,
+        long
+    )]
+    trace_ops: bool,
+    #[arg(
+        help = "Collect code coverage and write an LCOV report to the directory",
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        default_missing_value = "coverage",
+        require_equals = true,
+        num_args = ..=1,
+        long
+    )]
+    coverage: Option<PathBuf>,
+    #[arg(
+        help = "Write a JUnit XML report of the test results to the file",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        default_missing_value = "junit.xml",
+        require_equals = true,
+        num_args = ..=1,
+        long
+    )]
+    junit: Option<PathBuf>,
 }
 
 const PORT_RANGE: RangeInclusive<usize> = 1..=65535;
@@ -287,16 +341,15 @@ fn run_command(args: &RunArgs, globals: &GlobalArgs) {
         root,
         test_mode: false,
         expose_gc: globals.expose_gc.unwrap_or_default(),
+        ..Default::default()
     };
 
     // Create new JS runtime.
     let mut runtime = JsRuntime::with_options(options);
-    let mod_result = runtime.execute_module(&filename, None);
 
-    match mod_result {
-        Ok(_) => runtime.run_event_loop(),
-        Err(e) => eprintln!("{e:?}"),
-    };
+    if let Err(e) = runtime.execute_main_module(&filename) {
+        eprintln!("{e:?}");
+    }
 }
 
 fn test_command(args: &TestArgs, globals: &GlobalArgs) {
@@ -329,6 +382,24 @@ fn test_command(args: &TestArgs, globals: &GlobalArgs) {
         None => "undefined".into(),
     };
 
+    let shuffle = match args.shuffle {
+        Some(Some(seed)) => seed.to_string(),
+        Some(None) => "true".into(),
+        None => "undefined".into(),
+    };
+
+    let fail_fast = match args.fail_fast {
+        Some(Some(count)) => count.to_string(),
+        Some(None) => "true".into(),
+        None => "false".into(),
+    };
+
+    let concurrency = match args.parallel {
+        Some(Some(jobs)) => jobs as usize,
+        Some(None) => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        None => 1,
+    };
+
     // Check if we need to enable the inspector.
     let inspect = globals
         .inspect
@@ -341,16 +412,26 @@ fn test_command(args: &TestArgs, globals: &GlobalArgs) {
 
     env::set_var("TEST_ENTRY_PATH", String::from(test_path.to_string_lossy()));
 
+    if let Some(path) = args.junit.as_ref() {
+        env::set_var("TEST_JUNIT_PATH", String::from(path.to_string_lossy()));
+    }
+
     // Build JavaScript test script.
     let script = format!(
         "
         import {{ mainRunner }} from 'test';
+        import {{ snapshots }} from 'assert';
         mainRunner.failFast = {};
         mainRunner.filter = {};
+        mainRunner.shuffle = {};
+        mainRunner.concurrency = {};
+        mainRunner.traceOps = {};
+        mainRunner.junit = process.env.TEST_JUNIT_PATH;
+        snapshots.update = {};
         await mainRunner.importTests(process.env.TEST_ENTRY_PATH);
         await mainRunner.run();
     ",
-        args.fail_fast, filter,
+        fail_fast, filter, shuffle, concurrency, args.trace_ops, args.update,
     );
 
     // Build JS runtime options.
@@ -362,6 +443,7 @@ fn test_command(args: &TestArgs, globals: &GlobalArgs) {
         import_map,
         inspect,
         expose_gc: globals.expose_gc.unwrap_or_default(),
+        coverage: args.coverage.clone(),
         ..Default::default()
     };
 
@@ -369,9 +451,14 @@ fn test_command(args: &TestArgs, globals: &GlobalArgs) {
     let mut runtime = JsRuntime::with_options(options);
     let mod_result = runtime.execute_module("dune:environment/test", Some(&script));
 
+    // Note: The test runner usually exits the process (flushing the coverage
+    // report there), otherwise the event-loop flushes it once it's done.
     match mod_result {
         Ok(_) => runtime.run_event_loop(),
-        Err(e) => eprintln!("{e:?}"),
+        Err(e) => {
+            eprintln!("{e:?}");
+            coverage::flush(&mut runtime.handle_scope());
+        }
     };
 }
 
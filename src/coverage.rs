@@ -0,0 +1,500 @@
+// Code coverage collection, based on V8's precise (block) coverage.
+//
+// https://v8.dev/blog/javascript-code-coverage
+// https://chromedevtools.github.io/devtools-protocol/tot/Profiler/
+
+use crate::inspector::LocalInspectorSession;
+use crate::runtime::JsRuntime;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt::Write;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use url::Url;
+
+/// A range of source code (in UTF-16 offsets) along with its execution count.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageRange {
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub count: u64,
+}
+
+/// Coverage information of a single JavaScript function.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionCoverage {
+    pub function_name: String,
+    pub ranges: Vec<CoverageRange>,
+}
+
+/// Coverage information of a single script (or ES module).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptCoverage {
+    pub script_id: String,
+    pub url: String,
+    pub functions: Vec<FunctionCoverage>,
+    /// The source code V8 compiled (e.g. the output of the TypeScript compiler).
+    #[serde(skip)]
+    pub source: String,
+    /// Source ranges excluded from the report through ignore comments.
+    #[serde(skip)]
+    pub ignored: Vec<Range<usize>>,
+}
+
+impl ScriptCoverage {
+    /// Returns the local path of the script (file URLs are percent-decoded).
+    pub fn path(&self) -> String {
+        match Url::parse(&self.url) {
+            Ok(url) if url.scheme() == "file" => match url.to_file_path() {
+                Ok(path) => path.to_string_lossy().into_owned(),
+                Err(_) => url.path().into(),
+            },
+            _ => self.url.clone(),
+        }
+    }
+
+    /// Excludes the source ranges marked by ignore comments from the report:
+    ///
+    /// - `// dune-coverage-ignore` ignores the next line.
+    /// - `// dune-coverage-ignore-start` and `// dune-coverage-ignore-stop`
+    ///   ignore the lines between them.
+    /// - `// dune-coverage-ignore-file` ignores the whole file.
+    /// - `/* istanbul ignore next */` ignores the function or block that
+    ///   directly follows it.
+    pub fn apply_ignore_comments(&mut self, source: &str) {
+        const IGNORE_NEXT: &str = "/* istanbul ignore next */";
+
+        let units: Vec<u16> = source.encode_utf16().collect();
+        let is_whitespace = |c: &u16| char::from_u32(*c as u32).is_some_and(char::is_whitespace);
+
+        let mut ignored = vec![];
+        let mut start = None;
+        let mut ignore_line = false;
+        let mut offset = 0;
+
+        for line in source.split_inclusive('\n') {
+            let (line_start, line_end) = (offset, offset + line.encode_utf16().count());
+            offset = line_end;
+
+            if ignore_line {
+                ignored.push(line_start..line_end);
+                ignore_line = false;
+            }
+
+            for (position, _) in line.match_indices(IGNORE_NEXT) {
+                let after =
+                    line_start + line[..position + IGNORE_NEXT.len()].encode_utf16().count();
+
+                // Note: V8 reports no ranges for fully covered blocks, so only a range
+                // starting right after the comment belongs to it.
+                let next = self
+                    .functions
+                    .iter()
+                    .flat_map(|f| &f.ranges)
+                    .filter(|r| r.start_offset >= after && r.start_offset <= units.len())
+                    .filter(|r| units[after..r.start_offset].iter().all(is_whitespace))
+                    .max_by_key(|r| r.end_offset);
+
+                if let Some(range) = next {
+                    ignored.push(range.start_offset..range.end_offset);
+                }
+            }
+
+            let directive = match line.trim().strip_prefix("// dune-coverage-ignore") {
+                Some(directive) => directive,
+                None => continue,
+            };
+
+            match directive {
+                "-file" => {
+                    ignored.clear();
+                    ignored.push(0..units.len());
+                    break;
+                }
+                "-start" => start = start.or(Some(line_start)),
+                "-stop" => {
+                    if let Some(start) = start.take() {
+                        ignored.push(start..line_end);
+                    }
+                }
+                // Note: The comment may also explain why the line is ignored.
+                _ if directive.is_empty() || directive.starts_with(' ') => ignore_line = true,
+                _ => {}
+            }
+        }
+
+        // An unterminated ignore block extends until the end of the file.
+        if let Some(start) = start {
+            ignored.push(start..units.len());
+        }
+
+        self.ignored = ignored;
+    }
+
+    /// Checks if the given source range is excluded from the report.
+    fn is_ignored(&self, start: usize, end: usize) -> bool {
+        self.ignored
+            .iter()
+            .any(|range| range.start <= start && end <= range.end)
+    }
+
+    /// Returns the non-overlapping source segments along with the execution
+    /// count of the innermost range (V8 reports nested block ranges).
+    pub fn segments(&self) -> Vec<CoverageRange> {
+        let ranges: Vec<&CoverageRange> = self.functions.iter().flat_map(|f| &f.ranges).collect();
+
+        let mut offsets: Vec<usize> = ranges
+            .iter()
+            .flat_map(|r| [r.start_offset, r.end_offset])
+            .chain(self.ignored.iter().flat_map(|r| [r.start, r.end]))
+            .collect();
+
+        offsets.sort_unstable();
+        offsets.dedup();
+
+        let mut segments: Vec<CoverageRange> = vec![];
+
+        for window in offsets.windows(2) {
+            let (start, end) = (window[0], window[1]);
+
+            if self.is_ignored(start, end) {
+                continue;
+            }
+
+            // Find the innermost range containing this segment.
+            let innermost = ranges
+                .iter()
+                .filter(|r| r.start_offset <= start && end <= r.end_offset)
+                .min_by_key(|r| r.end_offset - r.start_offset);
+
+            let count = match innermost {
+                Some(range) => range.count,
+                None => continue,
+            };
+
+            // Merge with the previous segment when possible.
+            match segments.last_mut() {
+                Some(last) if last.end_offset == start && (last.count > 0) == (count > 0) => {
+                    last.end_offset = end;
+                    last.count = last.count.max(count);
+                }
+                _ => segments.push(CoverageRange {
+                    start_offset: start,
+                    end_offset: end,
+                    count,
+                }),
+            }
+        }
+
+        segments
+    }
+
+    /// Returns the source ranges that were executed at least once.
+    pub fn covered_ranges(&self) -> Vec<CoverageRange> {
+        self.segments()
+            .into_iter()
+            .filter(|r| r.count > 0)
+            .collect()
+    }
+
+    /// Returns the source ranges that were never executed.
+    pub fn uncovered_ranges(&self) -> Vec<CoverageRange> {
+        self.segments()
+            .into_iter()
+            .filter(|r| r.count == 0)
+            .collect()
+    }
+
+    /// Formats the coverage information as an LCOV record.
+    pub fn to_lcov(&self, path: &str, source: &str) -> String {
+        let source: Vec<u16> = source.encode_utf16().collect();
+        let segments = self.segments();
+
+        // Returns the execution count at the given offset.
+        let count_at = |offset: usize| {
+            segments
+                .iter()
+                .find(|r| r.start_offset <= offset && offset < r.end_offset)
+                .map(|r| r.count)
+        };
+
+        // Returns the (1-based) line number of the given offset.
+        let line_of = |offset: usize| {
+            let preceding = source[..offset.min(source.len())].iter();
+            preceding.filter(|c| **c == b'\n' as u16).count() + 1
+        };
+
+        let mut output = format!("SF:{path}\n");
+
+        // Report function hits (the top-level script is not a function).
+        let functions: Vec<&FunctionCoverage> = self
+            .functions
+            .iter()
+            .filter(|f| !f.function_name.is_empty())
+            .filter(|f| !self.is_ignored(f.ranges[0].start_offset, f.ranges[0].start_offset))
+            .collect();
+
+        for function in &functions {
+            let line = line_of(function.ranges[0].start_offset);
+            let _ = writeln!(output, "FN:{line},{}", function.function_name);
+        }
+
+        for function in &functions {
+            let count = function.ranges[0].count;
+            let _ = writeln!(output, "FNDA:{count},{}", function.function_name);
+        }
+
+        let hit = functions.iter().filter(|f| f.ranges[0].count > 0).count();
+        let _ = writeln!(output, "FNF:{}\nFNH:{hit}", functions.len());
+
+        // Report line hits, based on the line's first non-whitespace character.
+        let (mut found, mut hit) = (0, 0);
+        let mut offset = 0;
+
+        let is_whitespace = |c: &u16| char::from_u32(*c as u32).is_some_and(char::is_whitespace);
+
+        for (index, line) in source.split(|c| *c == b'\n' as u16).enumerate() {
+            let indent = line.iter().position(|c| !is_whitespace(c));
+
+            if let Some(count) = indent.and_then(|indent| count_at(offset + indent)) {
+                let _ = writeln!(output, "DA:{},{count}", index + 1);
+                found += 1;
+                hit += (count > 0) as usize;
+            }
+
+            offset += line.len() + 1;
+        }
+
+        let _ = writeln!(output, "LF:{found}\nLH:{hit}\nend_of_record");
+        output
+    }
+}
+
+/// Collects precise code coverage through an in-process inspector session.
+pub struct CoverageCollector {
+    session: Box<LocalInspectorSession>,
+}
+
+impl CoverageCollector {
+    /// Starts collecting precise coverage (call counts and block granularity).
+    pub fn start(mut session: Box<LocalInspectorSession>) -> Result<Self> {
+        // Note: The debugger is only enabled to read the compiled sources back,
+        // so it must never pause the scripts.
+        session.post("Debugger.enable", json!({}))?;
+        session.post("Debugger.setSkipAllPauses", json!({ "skip": true }))?;
+        session.post("Profiler.enable", json!({}))?;
+        session.post(
+            "Profiler.startPreciseCoverage",
+            json!({ "callCount": true, "detailed": true }),
+        )?;
+
+        Ok(Self { session })
+    }
+
+    /// Returns the coverage of the user scripts, collected so far.
+    pub fn take_report(&mut self) -> Result<Vec<ScriptCoverage>> {
+        let result = self
+            .session
+            .post("Profiler.takePreciseCoverage", json!({}))?;
+        let scripts: Vec<ScriptCoverage> = serde_json::from_value(result["result"].clone())?;
+
+        // Note: Internal modules (e.g. `dune:environment/main`) are excluded.
+        let mut scripts: Vec<ScriptCoverage> = scripts
+            .into_iter()
+            .filter(|script| !script.url.is_empty() && !script.url.starts_with("dune:"))
+            .collect();
+
+        // Note: The offsets refer to the compiled source, which differs from the
+        // file on disk for transpiled scripts (e.g. TypeScript).
+        for script in scripts.iter_mut() {
+            let params = json!({ "scriptId": script.script_id });
+            let source: String = match self.session.post("Debugger.getScriptSource", params) {
+                Ok(result) => result["scriptSource"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                Err(_) => continue,
+            };
+            script.apply_ignore_comments(&source);
+            script.source = source;
+        }
+
+        Ok(scripts)
+    }
+}
+
+/// Writes an LCOV report (`lcov.info`) for the scripts found locally.
+pub fn write_lcov_report(report: &[ScriptCoverage], dir: &Path) -> Result<()> {
+    let mut output = String::new();
+
+    for script in report {
+        let path = script.path();
+        if Path::new(&path).is_file() {
+            output.push_str(&script.to_lcov(&path, &script.source));
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("lcov.info"), output)?;
+
+    Ok(())
+}
+
+/// Writes the coverage report (if coverage is being collected).
+///
+/// Note: It must run before the inspector is told the context was destroyed.
+pub fn flush(scope: &mut v8::HandleScope) {
+    let state_rc = JsRuntime::state(scope);
+
+    let (dir, collector) = {
+        let mut state = state_rc.borrow_mut();
+        (state.options.coverage.clone(), state.coverage.take())
+    };
+
+    let (dir, mut collector) = match (dir, collector) {
+        (Some(dir), Some(collector)) => (dir, collector),
+        (_, collector) => {
+            state_rc.borrow_mut().coverage = collector;
+            return;
+        }
+    };
+
+    // Note: The state isn't borrowed while V8 answers the CDP commands.
+    let report = collector.take_report();
+    state_rc.borrow_mut().coverage = Some(collector);
+
+    if let Err(e) = report.and_then(|report| write_lcov_report(&report, &dir)) {
+        eprintln!("Failed to write the coverage report: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::JsRuntimeOptions;
+    use std::env;
+
+    #[test]
+    fn test_uncovered_branch_is_reported() {
+        let options = JsRuntimeOptions {
+            coverage: Some(env::temp_dir()),
+            ..Default::default()
+        };
+
+        let mut runtime = JsRuntime::with_options(options);
+        let source = "
+            function check(value) {
+                if (value) {
+                    return 'taken';
+                }
+                return 'not taken';
+            }
+            check(true);
+        ";
+
+        runtime
+            .execute_script("/coverage/branch.js", source)
+            .unwrap();
+
+        let report = runtime.coverage_report().unwrap();
+        let script = report
+            .iter()
+            .find(|script| script.url.ends_with("branch.js"))
+            .unwrap();
+
+        let source: Vec<u16> = source.encode_utf16().collect();
+        let snippet = |range: &CoverageRange| {
+            String::from_utf16_lossy(&source[range.start_offset..range.end_offset])
+        };
+
+        let uncovered: Vec<String> = script.uncovered_ranges().iter().map(snippet).collect();
+        let covered: Vec<String> = script.covered_ranges().iter().map(snippet).collect();
+
+        assert!(uncovered.iter().any(|code| code.contains("'not taken'")));
+        assert!(uncovered.iter().all(|code| !code.contains("'taken'")));
+        assert!(covered.iter().any(|code| code.contains("'taken'")));
+
+        // The untaken branch is reported as a line without hits.
+        let lcov = script.to_lcov("/coverage/branch.js", &String::from_utf16_lossy(&source));
+        assert!(lcov.contains("DA:6,0\n"));
+        assert!(lcov.contains("DA:4,1\n"));
+    }
+
+    #[test]
+    fn test_ignored_branch_is_excluded() {
+        let options = JsRuntimeOptions {
+            coverage: Some(env::temp_dir()),
+            ..Default::default()
+        };
+
+        let mut runtime = JsRuntime::with_options(options);
+        let source = "
+            function check(value) {
+                if (value) {
+                    return 'taken';
+                }
+                // dune-coverage-ignore
+                return 'ignored';
+            }
+            /* istanbul ignore next */
+            function unused() {
+                return 'also ignored';
+            }
+            /* istanbul ignore next */
+            check(true);
+            function later() {
+                return 'not ignored';
+            }
+        ";
+
+        // Note: Ignore comments are read from the source V8 compiled.
+        runtime
+            .execute_script("/coverage/ignore.js", source)
+            .unwrap();
+
+        let report = runtime.coverage_report().unwrap();
+        let script = report
+            .iter()
+            .find(|script| script.url.ends_with("ignore.js"))
+            .unwrap();
+
+        let source: Vec<u16> = source.encode_utf16().collect();
+        let snippet = |range: &CoverageRange| {
+            String::from_utf16_lossy(&source[range.start_offset..range.end_offset])
+        };
+
+        let uncovered: Vec<String> = script.uncovered_ranges().iter().map(snippet).collect();
+
+        assert!(uncovered.iter().all(|code| !code.contains("'ignored'")));
+        assert!(uncovered
+            .iter()
+            .all(|code| !code.contains("'also ignored'")));
+
+        // A comment without a range right after it doesn't hide later code.
+        assert!(uncovered.iter().any(|code| code.contains("'not ignored'")));
+
+        // The ignored lines are not reported at all.
+        let lcov = script.to_lcov(&script.path(), &script.source);
+        assert!(!lcov.contains("DA:7,"));
+        assert!(!lcov.contains("DA:11,"));
+        assert!(lcov.contains("DA:16,0\n"));
+    }
+
+    #[test]
+    fn test_file_urls_are_decoded() {
+        let script = ScriptCoverage {
+            script_id: "1".into(),
+            url: "file:///tmp/my%20project/index.js".into(),
+            functions: vec![],
+            source: String::new(),
+            ignored: vec![],
+        };
+
+        assert_eq!(script.path(), "/tmp/my project/index.js");
+    }
+}
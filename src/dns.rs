@@ -3,10 +3,15 @@ use crate::bindings::set_function_to;
 use crate::bindings::set_property_to;
 use crate::runtime::JsFuture;
 use crate::runtime::JsRuntime;
+use anyhow::bail;
 use anyhow::Result;
-use dns_lookup::lookup_host;
 use dune_event_loop::LoopHandle;
 use dune_event_loop::TaskResult;
+use hickory_resolver::proto::rr::RData;
+use hickory_resolver::proto::rr::RecordType;
+use hickory_resolver::Resolver;
+use serde::Deserialize;
+use serde::Serialize;
 use std::net::IpAddr;
 
 pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
@@ -14,6 +19,7 @@ pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     let target = v8::Object::new(scope);
 
     set_function_to(scope, target, "lookup", dns_lookup);
+    set_function_to(scope, target, "resolve", dns_resolve);
 
     // Return v8 global handle.
     v8::Global::new(scope, target)
@@ -113,7 +119,9 @@ fn dns_lookup(
 
 /// Pure rust implementation of a DNS lookup.
 fn dns_lookup_op(hostname: &str) -> Result<Vec<(String, String)>> {
-    Ok(lookup_host(hostname)?
+    let resolver = Resolver::from_system_conf()?;
+    Ok(resolver
+        .lookup_ip(hostname)?
         .iter()
         .map(|ip| match ip {
             IpAddr::V4(address) => (address.to_string(), "IPv4".into()),
@@ -121,3 +129,154 @@ fn dns_lookup_op(hostname: &str) -> Result<Vec<(String, String)>> {
         })
         .collect())
 }
+
+/// A DNS record returned by the resolver.
+#[derive(Debug, Serialize, Deserialize)]
+enum DnsRecord {
+    // An A or AAAA record.
+    Address(String),
+    // A CNAME record.
+    Name(String),
+    // An MX record (priority, exchange).
+    Mail(u16, String),
+    // A TXT record (the text chunks).
+    Text(Vec<String>),
+}
+
+/// Describes what will run after the async dns_resolve completes.
+struct DnsResolveFuture {
+    promise: v8::Global<v8::PromiseResolver>,
+    maybe_result: TaskResult,
+}
+
+impl JsFuture for DnsResolveFuture {
+    fn run(&mut self, scope: &mut v8::HandleScope) {
+        // Extract the result.
+        let result = self.maybe_result.take().unwrap();
+
+        // Handle when something goes wrong on the DNS resolution.
+        if let Err(e) = result {
+            let message = v8::String::new(scope, &e.to_string()).unwrap();
+            let exception = v8::Exception::error(scope, message);
+            set_exception_code(scope, exception, &e);
+            // Reject the promise on failure.
+            self.promise.open(scope).reject(scope, exception);
+            return;
+        }
+
+        // Otherwise, get the result and deserialize it.
+        let result = result.unwrap();
+        let result: Vec<DnsRecord> = bincode::deserialize(&result).unwrap();
+
+        let records: Vec<v8::Local<v8::Value>> = result
+            .iter()
+            .map(|record| match record {
+                DnsRecord::Address(value) | DnsRecord::Name(value) => {
+                    v8::String::new(scope, value).unwrap().into()
+                }
+                DnsRecord::Mail(priority, exchange) => {
+                    let mx = v8::Object::new(scope);
+                    let priority = v8::Integer::new(scope, *priority as i32).into();
+                    let exchange = v8::String::new(scope, exchange).unwrap().into();
+
+                    set_property_to(scope, mx, "priority", priority);
+                    set_property_to(scope, mx, "exchange", exchange);
+
+                    mx.into()
+                }
+                DnsRecord::Text(chunks) => {
+                    let chunks: Vec<v8::Local<v8::Value>> = chunks
+                        .iter()
+                        .map(|chunk| v8::String::new(scope, chunk).unwrap().into())
+                        .collect();
+
+                    v8::Array::new_with_elements(scope, &chunks).into()
+                }
+            })
+            .collect();
+
+        let records_array = v8::Array::new_with_elements(scope, &records);
+
+        self.promise
+            .open(scope)
+            .resolve(scope, records_array.into())
+            .unwrap();
+    }
+}
+
+/// Queries the DNS server for the records of a specific type.
+fn dns_resolve(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    // Get query and record type from the arguments provided.
+    let query = args.get(0).to_rust_string_lossy(scope);
+    let record_type = args.get(1).to_rust_string_lossy(scope);
+
+    // Create a promise resolver and extract the actual promise.
+    let promise_resolver = v8::PromiseResolver::new(scope).unwrap();
+    let promise = promise_resolver.get_promise(scope);
+
+    let state_rc = JsRuntime::state(scope);
+    let state = state_rc.borrow();
+
+    // The actual async task.
+    let task = move || match dns_resolve_op(&query, &record_type) {
+        Ok(result) => Some(Ok(bincode::serialize(&result).unwrap())),
+        Err(e) => Some(Result::Err(e)),
+    };
+
+    // The callback that will run after the above task completes.
+    let task_cb = {
+        let promise = v8::Global::new(scope, promise_resolver);
+        let state_rc = state_rc.clone();
+
+        move |_: LoopHandle, maybe_result: TaskResult| {
+            let mut state = state_rc.borrow_mut();
+            let future = DnsResolveFuture {
+                promise,
+                maybe_result,
+            };
+            state.pending_futures.push(Box::new(future));
+        }
+    };
+
+    state.handle.spawn(task, Some(task_cb));
+
+    rv.set(promise.into());
+}
+
+/// Pure rust implementation of a DNS resolution.
+fn dns_resolve_op(query: &str, record_type: &str) -> Result<Vec<DnsRecord>> {
+    // Map the requested record type to the resolver's type.
+    let record_type = match record_type {
+        "A" => RecordType::A,
+        "AAAA" => RecordType::AAAA,
+        "CNAME" => RecordType::CNAME,
+        "MX" => RecordType::MX,
+        "TXT" => RecordType::TXT,
+        _ => bail!("Unsupported DNS record type: {record_type}"),
+    };
+
+    let resolver = Resolver::from_system_conf()?;
+    let lookup = resolver.lookup(query, record_type)?;
+
+    Ok(lookup
+        .record_iter()
+        .filter(|record| record.record_type() == record_type)
+        .filter_map(|record| match record.data()? {
+            RData::A(address) => Some(DnsRecord::Address(address.to_string())),
+            RData::AAAA(address) => Some(DnsRecord::Address(address.to_string())),
+            RData::CNAME(name) => Some(DnsRecord::Name(name.0.to_utf8())),
+            RData::MX(mx) => Some(DnsRecord::Mail(mx.preference(), mx.exchange().to_utf8())),
+            RData::TXT(txt) => Some(DnsRecord::Text(
+                txt.txt_data()
+                    .iter()
+                    .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect())
+}
@@ -1,5 +1,7 @@
 use anyhow::Error;
 use colored::*;
+use serde::Serialize;
+use serde_json::json;
 use std::borrow::Cow;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -37,9 +39,22 @@ pub fn generic_error(message: impl Into<Cow<'static, str>>) -> Error {
     CustomError::new("Error", message)
 }
 
+/// The maximum number of (nested) causes kept by an error.
+const MAX_CAUSE_DEPTH: usize = 10;
+
+/// A single frame from the stack-trace of an exception.
+#[derive(Debug, Eq, PartialEq, Clone, Default, Serialize)]
+pub struct JsStackFrame {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub function: Option<String>,
+}
+
 /// Represents an exception coming from V8.
 #[derive(Eq, PartialEq, Clone, Default)]
 pub struct JsError {
+    pub name: Option<String>,
     pub message: String,
     pub resource_name: String,
     pub source_line: Option<String>,
@@ -47,6 +62,8 @@ pub struct JsError {
     pub start_column: Option<i64>,
     pub end_column: Option<i64>,
     pub stack: Option<String>,
+    pub frames: Vec<JsStackFrame>,
+    pub cause: Option<Box<JsError>>,
 }
 
 impl JsError {
@@ -55,6 +72,15 @@ impl JsError {
         scope: &'a mut v8::HandleScope,
         rejection: v8::Local<'a, v8::Value>,
         prefix: Option<&str>,
+    ) -> Self {
+        Self::from_exception(scope, rejection, prefix, 0)
+    }
+
+    fn from_exception<'a>(
+        scope: &'a mut v8::HandleScope,
+        rejection: v8::Local<'a, v8::Value>,
+        prefix: Option<&str>,
+        depth: usize,
     ) -> Self {
         // Create a new HandleScope.
         let scope = &mut v8::HandleScope::new(scope);
@@ -101,7 +127,46 @@ impl JsError {
             .map(|stack| stack.unwrap_or_default())
             .ok();
 
+        // Read the error's name and cause (if it's an object).
+        let name = exception
+            .ok()
+            .and_then(|exception| get_property(scope, exception, "name"))
+            .map(|name| name.to_rust_string_lossy(scope));
+
+        let cause = exception
+            .ok()
+            .filter(|_| depth < MAX_CAUSE_DEPTH)
+            .and_then(|exception| get_property(scope, exception, "cause"))
+            .map(|cause| Self::from_exception(scope, cause, None, depth + 1))
+            .map(Box::new);
+
+        // Note: Errors keep the stack-trace of where they were created, even
+        // when thrown later (or never thrown, like causes).
+        let stack_trace = v8::Exception::get_stack_trace(scope, rejection)
+            .or_else(|| message.get_stack_trace(scope));
+
+        let mut frames = vec![];
+        if let Some(stack_trace) = stack_trace {
+            for index in 0..stack_trace.get_frame_count() {
+                let Some(frame) = stack_trace.get_frame(scope, index) else {
+                    continue;
+                };
+                frames.push(JsStackFrame {
+                    file: frame
+                        .get_script_name(scope)
+                        .map(|name| name.to_rust_string_lossy(scope)),
+                    line: frame.get_line_number(),
+                    column: frame.get_column(),
+                    function: frame
+                        .get_function_name(scope)
+                        .map(|name| name.to_rust_string_lossy(scope))
+                        .filter(|name| !name.is_empty()),
+                });
+            }
+        }
+
         JsError {
+            name,
             message: message_value,
             resource_name,
             source_line,
@@ -109,8 +174,40 @@ impl JsError {
             start_column,
             end_column,
             stack,
+            frames,
+            cause,
         }
     }
+
+    /// Returns a machine-readable version of the error, as
+    /// `{ name, message, frames: [{ file, line, column, function }], cause }`.
+    pub fn to_json(&self) -> serde_json::Value {
+        // Note: V8 messages are prefixed with the error's name (e.g. `Error: `).
+        let message = self
+            .name
+            .as_ref()
+            .and_then(|name| self.message.strip_prefix(&format!("{name}: ")))
+            .unwrap_or(&self.message);
+
+        json!({
+            "name": self.name,
+            "message": message,
+            "frames": self.frames,
+            "cause": self.cause.as_ref().map(|cause| cause.to_json()),
+        })
+    }
+}
+
+// Returns the value of an object's property (unless it's undefined).
+fn get_property<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    object: v8::Local<v8::Object>,
+    name: &str,
+) -> Option<v8::Local<'s, v8::Value>> {
+    let key = v8::String::new(scope, name).unwrap();
+    object
+        .get(scope, key.into())
+        .filter(|value| !value.is_undefined())
 }
 
 impl std::error::Error for JsError {}
@@ -185,25 +282,67 @@ pub fn report_and_exit(error: JsError) {
     std::process::exit(1);
 }
 
-/// Returns a string representation of the IO error's code.
+/// Returns the POSIX style code (e.g. "ENOENT") of the IO error.
 pub fn extract_error_code(err: &IoError) -> Option<&'static str> {
     match err.kind() {
-        ErrorKind::AddrInUse => Some("ADDR_IN_USE"),
-        ErrorKind::AddrNotAvailable => Some("ADDR_NOT_AVAILABLE"),
-        ErrorKind::AlreadyExists => Some("ALREADY_EXISTS"),
-        ErrorKind::BrokenPipe => Some("BROKEN_PIPE"),
-        ErrorKind::ConnectionAborted => Some("CONNECTION_ABORTED"),
-        ErrorKind::ConnectionRefused => Some("CONNECTION_REFUSED"),
-        ErrorKind::ConnectionReset => Some("CONNECTION_RESET"),
-        ErrorKind::Interrupted => Some("INTERRUPTED"),
-        ErrorKind::InvalidData => Some("INVALID_DATA"),
-        ErrorKind::NotConnected => Some("NOT_CONNECTED"),
-        ErrorKind::NotFound => Some("NOT_FOUND"),
-        ErrorKind::PermissionDenied => Some("PERMISSION_DENIED"),
-        ErrorKind::TimedOut => Some("TIMED_OUT"),
-        ErrorKind::UnexpectedEof => Some("UNEXPECTED_EOF"),
-        ErrorKind::WouldBlock => Some("WOULD_BLOCK"),
-        ErrorKind::WriteZero => Some("WRITE_ZERO"),
+        ErrorKind::AddrInUse => Some("EADDRINUSE"),
+        ErrorKind::AddrNotAvailable => Some("EADDRNOTAVAIL"),
+        ErrorKind::AlreadyExists => Some("EEXIST"),
+        ErrorKind::BrokenPipe => Some("EPIPE"),
+        ErrorKind::ConnectionAborted => Some("ECONNABORTED"),
+        ErrorKind::ConnectionRefused => Some("ECONNREFUSED"),
+        ErrorKind::ConnectionReset => Some("ECONNRESET"),
+        ErrorKind::Interrupted => Some("EINTR"),
+        ErrorKind::InvalidData => Some("EINVAL"),
+        ErrorKind::InvalidInput => Some("EINVAL"),
+        ErrorKind::NotConnected => Some("ENOTCONN"),
+        ErrorKind::NotFound => Some("ENOENT"),
+        // Note: Rust reports both EPERM (errno 1) and EACCES as `PermissionDenied`.
+        ErrorKind::PermissionDenied if cfg!(unix) && err.raw_os_error() == Some(1) => Some("EPERM"),
+        ErrorKind::PermissionDenied => Some("EACCES"),
+        ErrorKind::TimedOut => Some("ETIMEDOUT"),
+        ErrorKind::UnexpectedEof => Some("EOF"),
+        ErrorKind::WouldBlock => Some("EAGAIN"),
+        ErrorKind::WriteZero => Some("EIO"),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::JsRuntime;
+
+    #[test]
+    fn test_js_error_to_json() {
+        let mut runtime = JsRuntime::new();
+        let source = "function fail() {
+            throw new Error('boom', { cause: new TypeError('bad input') });
+        }
+        fail();";
+
+        let error = runtime
+            .execute_script("/errors/fail.js", source)
+            .err()
+            .unwrap()
+            .downcast::<JsError>()
+            .unwrap();
+
+        let json = error.to_json();
+
+        assert_eq!(json["name"], "Error");
+        assert_eq!(json["message"], "boom");
+        assert_eq!(json["frames"][0]["file"], "/errors/fail.js");
+        assert_eq!(json["frames"][0]["line"], 2);
+        assert_eq!(json["frames"][0]["function"], "fail");
+        assert!(json["frames"][0]["column"].is_u64());
+        assert_eq!(json["frames"][1]["line"], 4);
+        assert_eq!(json["cause"]["name"], "TypeError");
+        assert_eq!(json["cause"]["message"], "bad input");
+        assert!(json["cause"]["cause"].is_null());
+
+        // The human-readable version is left unchanged.
+        let display = error.to_string();
+        assert!(display.contains("Error: boom (/errors/fail.js:2:"));
+    }
+}
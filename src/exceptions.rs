@@ -8,20 +8,26 @@ pub struct ExceptionState {
     pub exception: Option<v8::Global<v8::Value>>,
     /// Holds uncaught promise rejections.
     pub promise_rejections: Vec<PromiseRejectionEntry>,
+    /// Holds the rejections that overflowed the buffer (reported on the next check).
+    pub overflowed_rejections: Vec<PromiseRejectionEntry>,
     /// Hook to run on an uncaught exception.
     pub uncaught_exception_cb: Option<v8::Global<v8::Function>>,
     /// Hook to run on an uncaught promise rejection.
     pub unhandled_rejection_cb: Option<v8::Global<v8::Function>>,
+    /// The maximum number of promise rejections kept until they're reported.
+    pub max_promise_rejections: Option<usize>,
 }
 
 impl ExceptionState {
-    /// Creates a new store with given report policy.
-    pub fn new() -> Self {
+    /// Creates a new store with given report policy (and rejections limit).
+    pub fn new(max_promise_rejections: Option<usize>) -> Self {
         ExceptionState {
             exception: None,
             promise_rejections: Vec::default(),
+            overflowed_rejections: Vec::default(),
             uncaught_exception_cb: None,
             unhandled_rejection_cb: None,
+            max_promise_rejections,
         }
     }
 
@@ -41,20 +47,45 @@ impl ExceptionState {
         self.promise_rejections.push((promise, reason));
     }
 
+    /// Moves the oldest promise rejections exceeding the limit to the overflow
+    /// queue, which is reported on the next exceptions check.
+    pub fn flush_overflowing_rejections(&mut self) {
+        if let Some(limit) = self.max_promise_rejections {
+            if self.promise_rejections.len() > limit {
+                let overflow = self.promise_rejections.len() - limit;
+                let entries = self.promise_rejections.drain(..overflow);
+                self.overflowed_rejections.extend(entries);
+            }
+        }
+    }
+
+    /// Takes all the rejections to report (the overflowed ones first).
+    pub fn take_promise_rejections(&mut self) -> Vec<PromiseRejectionEntry> {
+        let mut rejections: Vec<PromiseRejectionEntry> =
+            self.overflowed_rejections.drain(..).collect();
+        rejections.append(&mut self.promise_rejections);
+        rejections
+    }
+
     pub fn has_promise_rejection(&self) -> bool {
-        !self.promise_rejections.is_empty()
+        !self.promise_rejections.is_empty() || !self.overflowed_rejections.is_empty()
     }
 
     pub fn remove_promise_rejection(&mut self, promise: &v8::Global<v8::Promise>) {
-        // Find the correct entry to remove.
+        // Find the correct entry to remove (a handler may still be attached to
+        // an overflowed rejection before it gets reported).
         self.promise_rejections
             .retain(|(value, _)| value != promise);
+        self.overflowed_rejections
+            .retain(|(value, _)| value != promise);
     }
 
     pub fn remove_promise_rejection_entry(&mut self, exception: &v8::Global<v8::Value>) {
         // Find the correct entry to remove.
         self.promise_rejections
             .retain(|(_, value)| value != exception);
+        self.overflowed_rejections
+            .retain(|(_, value)| value != exception);
     }
 
     pub fn set_uncaught_exception_callback(&mut self, callback: Option<v8::Global<v8::Function>>) {
@@ -19,7 +19,9 @@ use serde::Serialize;
 use std::ffi::OsString;
 use std::fs;
 use std::fs::File;
+use std::fs::FileTimes;
 use std::fs::OpenOptions;
+use std::fs::TryLockError;
 use std::io::prelude::*;
 use std::io::SeekFrom;
 use std::path::Path;
@@ -79,8 +81,17 @@ pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     set_function_to(scope, target, "rmSync", rm_sync);
     set_function_to(scope, target, "close", close);
     set_function_to(scope, target, "closeSync", close_sync);
+    set_function_to(scope, target, "lock", lock);
+    set_function_to(scope, target, "lockSync", lock_sync);
+    set_function_to(scope, target, "tryLockSync", try_lock_sync);
+    set_function_to(scope, target, "unlock", unlock);
+    set_function_to(scope, target, "unlockSync", unlock_sync);
     set_function_to(scope, target, "rename", rename);
     set_function_to(scope, target, "renameSync", rename_sync);
+    set_function_to(scope, target, "chown", chown);
+    set_function_to(scope, target, "chownSync", chown_sync);
+    set_function_to(scope, target, "utime", utime);
+    set_function_to(scope, target, "utimeSync", utime_sync);
     set_function_to(scope, target, "watch", watch);
     set_function_to(scope, target, "unwatch", unwatch);
 
@@ -137,8 +148,8 @@ fn open(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, mut rv
     // Get file path.
     let path = args.get(0).to_rust_string_lossy(scope);
 
-    // Get flags which can be used to configure how a file is opened.
-    let flags = args.get(1).to_rust_string_lossy(scope);
+    // Get flags (or options) which can be used to configure how a file is opened.
+    let options = get_open_options(scope, args.get(1));
 
     // Create a promise resolver and extract the actual promise.
     let promise_resolver = v8::PromiseResolver::new(scope).unwrap();
@@ -148,7 +159,7 @@ fn open(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, mut rv
     let state = state_rc.borrow();
 
     // The actual async task.
-    let task = move || match open_file_op(path, flags) {
+    let task = move || match open_file_op(path, options) {
         Ok(result) => Some(Ok(bincode::serialize(&result).unwrap())),
         Err(e) => Some(Result::Err(e)),
     };
@@ -183,10 +194,10 @@ fn open_sync(
     // Get file path.
     let path = args.get(0).to_rust_string_lossy(scope);
 
-    // Get flags which can be used to configure how a file is opened.
-    let flags = args.get(1).to_rust_string_lossy(scope);
+    // Get flags (or options) which can be used to configure how a file is opened.
+    let options = get_open_options(scope, args.get(1));
 
-    match open_file_op(path, flags) {
+    match open_file_op(path, options) {
         Ok(file_ptr) => {
             let file = get_file_reference(file_ptr);
             let file_wrapper = v8::ObjectTemplate::new(scope);
@@ -511,10 +522,17 @@ impl JsFuture for FsStatFuture {
     }
 }
 
+/// Returns whether symbolic links should be followed (the default) when
+/// getting the file statistics.
+fn follow_symlinks(value: v8::Local<v8::Value>) -> bool {
+    value.is_undefined() || value.is_true()
+}
+
 /// Get's asynchronously file statistics.
 fn stat(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, mut rv: v8::ReturnValue) {
     // Get the path.
     let path = args.get(0).to_rust_string_lossy(scope);
+    let follow = follow_symlinks(args.get(1));
 
     // Create a promise resolver and extract the actual promise.
     let promise_resolver = v8::PromiseResolver::new(scope).unwrap();
@@ -523,7 +541,7 @@ fn stat(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, mut rv
     let state_rc = JsRuntime::state(scope);
     let state = state_rc.borrow();
 
-    let task = move || match stats_op(path) {
+    let task = move || match stats_op(path, follow) {
         Ok(result) => Some(Ok(bincode::serialize(&result).unwrap())),
         Err(e) => Some(Result::Err(e)),
     };
@@ -556,8 +574,9 @@ fn stat_sync(
 ) {
     // Get the path.
     let path = args.get(0).to_rust_string_lossy(scope);
+    let follow = follow_symlinks(args.get(1));
 
-    match stats_op(path) {
+    match stats_op(path, follow) {
         Ok(stats) => rv.set(create_v8_stats_object(scope, stats).into()),
         Err(e) => throw_exception(scope, &e),
     };
@@ -985,6 +1004,166 @@ fn close_sync(
     throw_exception(scope, &anyhow!("File is closed."));
 }
 
+/// Describes what will run after the async lock_file_op (or unlock_file_op) completes.
+struct FsLockFuture {
+    promise: v8::Global<v8::PromiseResolver>,
+    maybe_result: TaskResult,
+}
+
+impl JsFuture for FsLockFuture {
+    fn run(&mut self, scope: &mut v8::HandleScope) {
+        // If the result is None then (un)locking worked.
+        if self.maybe_result.is_none() {
+            let undefined = v8::undefined(scope);
+            self.promise
+                .open(scope)
+                .resolve(scope, undefined.into())
+                .unwrap();
+
+            return;
+        }
+
+        // Something went wrong.
+        let result = self.maybe_result.take().unwrap();
+
+        // Something went wrong while (un)locking the file.
+        if let Err(e) = result {
+            let message = v8::String::new(scope, &e.to_string()).unwrap();
+            let exception = v8::Exception::error(scope, message);
+            set_exception_code(scope, exception, &e);
+            self.promise.open(scope).reject(scope, exception);
+            return;
+        }
+
+        unreachable!();
+    }
+}
+
+/// Spawns an async (un)lock task for an open file.
+fn spawn_lock_task<F>(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+    op: F,
+) where
+    F: FnOnce(&File) -> Result<()> + Send + 'static,
+{
+    // Get the file_wrap object.
+    let file_wrap = args.get(0).to_object(scope).unwrap();
+
+    // Create a promise resolver and extract the actual promise.
+    let promise_resolver = v8::PromiseResolver::new(scope).unwrap();
+    let promise = promise_resolver.get_promise(scope);
+
+    // Check if the file is already closed, otherwise create a file reference.
+    let file = match get_internal_ref::<Option<File>>(scope, file_wrap, 0) {
+        Some(file) => file.try_clone().unwrap(),
+        None => {
+            let message = v8::String::new(scope, "File is closed.").unwrap();
+            let exception = v8::Exception::error(scope, message);
+            promise_resolver.reject(scope, exception);
+            rv.set(promise.into());
+            return;
+        }
+    };
+
+    let state_rc = JsRuntime::state(scope);
+    let state = state_rc.borrow();
+
+    // The actual async task.
+    let task = move || match op(&file) {
+        Ok(_) => None,
+        Err(e) => Some(Result::Err(e)),
+    };
+
+    // The callback that will run after the above task completes.
+    let task_cb = {
+        let promise = v8::Global::new(scope, promise_resolver);
+        let state_rc = state_rc.clone();
+
+        move |_: LoopHandle, maybe_result: TaskResult| {
+            let mut state = state_rc.borrow_mut();
+            let future = FsLockFuture {
+                promise,
+                maybe_result,
+            };
+            state.pending_futures.push(Box::new(future));
+        }
+    };
+
+    // Spawn the async task using the event-loop.
+    state.handle.spawn(task, Some(task_cb));
+
+    rv.set(promise.into());
+}
+
+/// Acquires an advisory lock on a file asynchronously (waiting if needed).
+fn lock(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, rv: v8::ReturnValue) {
+    let exclusive = args.get(1).boolean_value(scope);
+    spawn_lock_task(scope, args, rv, move |file| lock_file_op(file, exclusive));
+}
+
+/// Acquires an advisory lock on a file synchronously (waiting if needed).
+fn lock_sync(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
+    // Get the file_wrap object.
+    let file_wrap = args.get(0).to_object(scope).unwrap();
+    let exclusive = args.get(1).boolean_value(scope);
+
+    let result = match get_internal_ref::<Option<File>>(scope, file_wrap, 0) {
+        Some(file) => lock_file_op(file, exclusive),
+        None => Err(anyhow!("File is closed.")),
+    };
+
+    if let Err(e) = result {
+        throw_exception(scope, &e);
+    }
+}
+
+/// Tries to acquire an advisory lock on a file without waiting.
+fn try_lock_sync(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    // Get the file_wrap object.
+    let file_wrap = args.get(0).to_object(scope).unwrap();
+    let exclusive = args.get(1).boolean_value(scope);
+
+    let result = match get_internal_ref::<Option<File>>(scope, file_wrap, 0) {
+        Some(file) => try_lock_file_op(file, exclusive),
+        None => Err(anyhow!("File is closed.")),
+    };
+
+    match result {
+        Ok(acquired) => rv.set(v8::Boolean::new(scope, acquired).into()),
+        Err(e) => throw_exception(scope, &e),
+    }
+}
+
+/// Releases an advisory lock on a file asynchronously.
+fn unlock(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, rv: v8::ReturnValue) {
+    spawn_lock_task(scope, args, rv, unlock_file_op);
+}
+
+/// Releases an advisory lock on a file synchronously.
+fn unlock_sync(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+) {
+    // Get the file_wrap object.
+    let file_wrap = args.get(0).to_object(scope).unwrap();
+
+    let result = match get_internal_ref::<Option<File>>(scope, file_wrap, 0) {
+        Some(file) => unlock_file_op(file),
+        None => Err(anyhow!("File is closed.")),
+    };
+
+    if let Err(e) = result {
+        throw_exception(scope, &e);
+    }
+}
+
 /// Describes what will run after the async rename_op completes.
 struct FsRenameFuture {
     promise: v8::Global<v8::PromiseResolver>,
@@ -1079,6 +1258,206 @@ fn rename_sync(
     }
 }
 
+/// Describes what will run after the async chown_op completes.
+struct FsChownFuture {
+    promise: v8::Global<v8::PromiseResolver>,
+    maybe_result: TaskResult,
+}
+
+impl JsFuture for FsChownFuture {
+    fn run(&mut self, scope: &mut v8::HandleScope) {
+        // If the result is None then changing the ownership worked.
+        if self.maybe_result.is_none() {
+            let undefined = v8::undefined(scope);
+            self.promise
+                .open(scope)
+                .resolve(scope, undefined.into())
+                .unwrap();
+
+            return;
+        }
+
+        // Something went wrong.
+        let result = self.maybe_result.take().unwrap();
+
+        // Something went wrong while changing the file's ownership.
+        if let Err(e) = result {
+            let message = v8::String::new(scope, &e.to_string()).unwrap();
+            let exception = v8::Exception::error(scope, message);
+            set_exception_code(scope, exception, &e);
+            self.promise.open(scope).reject(scope, exception);
+            return;
+        }
+
+        unreachable!();
+    }
+}
+
+/// Reads an optional user or group id (`null` means "leave unchanged").
+fn get_owner_id(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> Option<u32> {
+    match value.is_null_or_undefined() {
+        true => None,
+        false => value.uint32_value(scope),
+    }
+}
+
+/// Changes the owner and group of a file asynchronously.
+fn chown(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    // Get the path, uid and gid values.
+    let path = args.get(0).to_rust_string_lossy(scope);
+    let uid = get_owner_id(scope, args.get(1));
+    let gid = get_owner_id(scope, args.get(2));
+
+    // Create a promise resolver and extract the actual promise.
+    let promise_resolver = v8::PromiseResolver::new(scope).unwrap();
+    let promise = promise_resolver.get_promise(scope);
+
+    let state_rc = JsRuntime::state(scope);
+    let state = state_rc.borrow();
+
+    // The actual async task.
+    let task = move || match chown_op(path, uid, gid) {
+        Ok(_) => None,
+        Err(e) => Some(Result::Err(e)),
+    };
+
+    // The callback that will run after the above task completes.
+    let task_cb = {
+        let promise = v8::Global::new(scope, promise_resolver);
+        let state_rc = state_rc.clone();
+
+        move |_: LoopHandle, maybe_result: TaskResult| {
+            let mut state = state_rc.borrow_mut();
+            let future = FsChownFuture {
+                promise,
+                maybe_result,
+            };
+            state.pending_futures.push(Box::new(future));
+        }
+    };
+
+    // Spawn the async task using the event-loop.
+    state.handle.spawn(task, Some(task_cb));
+
+    rv.set(promise.into());
+}
+
+/// Changes the owner and group of a file synchronously.
+fn chown_sync(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+) {
+    // Get the path, uid and gid values.
+    let path = args.get(0).to_rust_string_lossy(scope);
+    let uid = get_owner_id(scope, args.get(1));
+    let gid = get_owner_id(scope, args.get(2));
+
+    if let Err(e) = chown_op(path, uid, gid) {
+        throw_exception(scope, &e);
+    }
+}
+
+/// Describes what will run after the async utime_op completes.
+struct FsUtimeFuture {
+    promise: v8::Global<v8::PromiseResolver>,
+    maybe_result: TaskResult,
+}
+
+impl JsFuture for FsUtimeFuture {
+    fn run(&mut self, scope: &mut v8::HandleScope) {
+        // If the result is None then changing the timestamps worked.
+        if self.maybe_result.is_none() {
+            let undefined = v8::undefined(scope);
+            self.promise
+                .open(scope)
+                .resolve(scope, undefined.into())
+                .unwrap();
+
+            return;
+        }
+
+        // Something went wrong.
+        let result = self.maybe_result.take().unwrap();
+
+        // Something went wrong while changing the file's timestamps.
+        if let Err(e) = result {
+            let message = v8::String::new(scope, &e.to_string()).unwrap();
+            let exception = v8::Exception::error(scope, message);
+            set_exception_code(scope, exception, &e);
+            self.promise.open(scope).reject(scope, exception);
+            return;
+        }
+
+        unreachable!();
+    }
+}
+
+/// Changes the access and modification times of a file asynchronously.
+fn utime(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    // Get the path, atime and mtime (in seconds) values.
+    let path = args.get(0).to_rust_string_lossy(scope);
+    let atime = args.get(1).number_value(scope).unwrap();
+    let mtime = args.get(2).number_value(scope).unwrap();
+
+    // Create a promise resolver and extract the actual promise.
+    let promise_resolver = v8::PromiseResolver::new(scope).unwrap();
+    let promise = promise_resolver.get_promise(scope);
+
+    let state_rc = JsRuntime::state(scope);
+    let state = state_rc.borrow();
+
+    // The actual async task.
+    let task = move || match utime_op(path, atime, mtime) {
+        Ok(_) => None,
+        Err(e) => Some(Result::Err(e)),
+    };
+
+    // The callback that will run after the above task completes.
+    let task_cb = {
+        let promise = v8::Global::new(scope, promise_resolver);
+        let state_rc = state_rc.clone();
+
+        move |_: LoopHandle, maybe_result: TaskResult| {
+            let mut state = state_rc.borrow_mut();
+            let future = FsUtimeFuture {
+                promise,
+                maybe_result,
+            };
+            state.pending_futures.push(Box::new(future));
+        }
+    };
+
+    // Spawn the async task using the event-loop.
+    state.handle.spawn(task, Some(task_cb));
+
+    rv.set(promise.into());
+}
+
+/// Changes the access and modification times of a file synchronously.
+fn utime_sync(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+) {
+    // Get the path, atime and mtime (in seconds) values.
+    let path = args.get(0).to_rust_string_lossy(scope);
+    let atime = args.get(1).number_value(scope).unwrap();
+    let mtime = args.get(2).number_value(scope).unwrap();
+
+    if let Err(e) = utime_op(path, atime, mtime) {
+        throw_exception(scope, &e);
+    }
+}
+
 struct WatchFuture {
     event: FsEvent,
     on_event_cb: Rc<v8::Global<v8::Function>>,
@@ -1182,31 +1561,91 @@ fn get_file_reference(handle: usize) -> File {
     unsafe { fs::File::from_raw_handle(handle as RawHandle) }
 }
 
+/// Options which can be used to configure how a file is opened.
+#[derive(Default, Debug, Clone)]
+struct OpenFileOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    truncate: bool,
+    create: bool,
+    create_new: bool,
+    mode: Option<u32>,
+}
+
+impl OpenFileOptions {
+    /// Returns the options described by the given flags (e.g. "r+" or "w").
+    fn from_flags(flags: &str) -> Self {
+        Self {
+            read: flags == "r" || flags == "r+" || flags == "w+" || flags == "a+",
+            write: flags == "r+" || flags == "w" || flags == "w+",
+            append: flags == "a" || flags == "a+",
+            truncate: flags == "w+",
+            create: flags == "w" || flags == "w+" || flags == "a" || flags == "a+",
+            create_new: false,
+            mode: None,
+        }
+    }
+}
+
+/// Reads the open options from either a flags string or an options object.
+fn get_open_options(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> OpenFileOptions {
+    let object = match value.to_object(scope) {
+        Some(object) if value.is_object() => object,
+        _ => return OpenFileOptions::from_flags(&value.to_rust_string_lossy(scope)),
+    };
+
+    let mut get_option = |name: &str| {
+        let key = v8::String::new(scope, name).unwrap();
+        let value = object.get(scope, key.into())?;
+        match value.is_null_or_undefined() {
+            true => None,
+            false => Some((value.boolean_value(scope), value.uint32_value(scope))),
+        }
+    };
+
+    let mut flag = |name: &str| get_option(name).is_some_and(|(value, _)| value);
+
+    OpenFileOptions {
+        read: flag("read"),
+        write: flag("write"),
+        append: flag("append"),
+        truncate: flag("truncate"),
+        create: flag("create"),
+        create_new: flag("createNew"),
+        mode: get_option("mode").and_then(|(_, mode)| mode),
+    }
+}
+
 /// Pure rust implementation of opening a file.
-fn open_file_op<P: AsRef<Path>>(path: P, flags: String) -> Result<usize> {
-    // Options and flags which can be used to configure how a file is opened.
-    let read = flags == "r" || flags == "r+" || flags == "w+" || flags == "a+";
-    let write = flags == "r+" || flags == "w" || flags == "w+";
-    let create = flags == "w" || flags == "w+" || flags == "a" || flags == "a+";
-    let truncate = flags == "w+";
-    let append = flags == "a" || flags == "a+";
+fn open_file_op<P: AsRef<Path>>(path: P, options: OpenFileOptions) -> Result<usize> {
+    let mut open_options = OpenOptions::new();
+    open_options
+        .read(options.read)
+        .write(options.write)
+        .create(options.create)
+        .create_new(options.create_new)
+        .append(options.append)
+        .truncate(options.truncate);
+
+    // Note: The mode is only applied to newly created files (and is masked by
+    // the process's umask), so they're never accessible with other permissions.
+    #[cfg(target_family = "unix")]
+    if let Some(mode) = options.mode {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(mode);
+    }
+
+    let file = open_options.open(path)?;
 
     // Note: The reason we leak the wrapped file handle is to prevent rust
     // from dropping the handle (a.k.a close the file) when current scope ends.
-    match OpenOptions::new()
-        .read(read)
-        .write(write)
-        .create(create)
-        .append(append)
-        .truncate(truncate)
-        .open(path)
-    {
-        #[cfg(target_family = "unix")]
-        Ok(file) => Ok(Box::leak(Box::new(file)).as_raw_fd() as usize),
-        #[cfg(target_family = "windows")]
-        Ok(file) => Ok(Box::leak(Box::new(file)).as_raw_handle() as usize),
-        Err(e) => bail!(e),
-    }
+    #[cfg(target_family = "unix")]
+    let handle = Box::leak(Box::new(file)).as_raw_fd() as usize;
+    #[cfg(target_family = "windows")]
+    let handle = Box::leak(Box::new(file)).as_raw_handle() as usize;
+
+    Ok(handle)
 }
 
 /// Pure rust implementation of reading a chunk from a file.
@@ -1234,10 +1673,43 @@ fn write_file_op(file: &mut File, buffer: &[u8]) -> Result<()> {
     Ok(())
 }
 
+/// Acquires an exclusive (or shared) advisory lock, using `flock` or `LockFileEx`.
+fn lock_file_op(file: &File, exclusive: bool) -> Result<()> {
+    match exclusive {
+        true => file.lock(),
+        false => file.lock_shared(),
+    }
+    .map_err(|e| anyhow!(e))
+}
+
+/// Tries to acquire an advisory lock, returning `false` if it's held elsewhere.
+fn try_lock_file_op(file: &File, exclusive: bool) -> Result<bool> {
+    let result = match exclusive {
+        true => file.try_lock(),
+        false => file.try_lock_shared(),
+    };
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(TryLockError::WouldBlock) => Ok(false),
+        Err(TryLockError::Error(e)) => bail!(e),
+    }
+}
+
+/// Releases an advisory lock held by the file.
+fn unlock_file_op(file: &File) -> Result<()> {
+    file.unlock().map_err(|e| anyhow!(e))
+}
+
 /// Pure rust implementation of getting file statistics.
-fn stats_op<P: AsRef<Path>>(path: P) -> Result<FileStatistics> {
-    // Try get file's metadata information.
-    match fs::metadata(path) {
+fn stats_op<P: AsRef<Path>>(path: P, follow_symlinks: bool) -> Result<FileStatistics> {
+    // Try get file's (or the symbolic link's own) metadata information.
+    let metadata = match follow_symlinks {
+        true => fs::metadata(path),
+        false => fs::symlink_metadata(path),
+    };
+
+    match metadata {
         Ok(metadata) => {
             // Returns the size of the file, in bytes, this metadata is for.
             let size = metadata.len();
@@ -1325,7 +1797,7 @@ fn readdir_op<P: AsRef<Path>>(path: P) -> Result<Vec<OsString>> {
 
 /// Pure rust implementation of deleting files and directories.
 fn rm_op<P: AsRef<Path>>(path: P) -> Result<()> {
-    if stats_op(&path)?.is_directory {
+    if stats_op(&path, true)?.is_directory {
         fs::remove_dir_all(&path).map_err(|e| anyhow!(e))?;
         return Ok(());
     }
@@ -1337,6 +1809,34 @@ fn rename_op<P: AsRef<Path>>(from: P, to: P) -> Result<()> {
     fs::rename(from, to).map_err(|e| anyhow!(e))
 }
 
+/// Changes the ownership of a file (Unix only).
+#[cfg(target_family = "unix")]
+fn chown_op<P: AsRef<Path>>(path: P, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
+    std::os::unix::fs::chown(path, uid, gid).map_err(|e| anyhow!(e))
+}
+
+/// Changes the ownership of a file (Unix only).
+#[cfg(target_family = "windows")]
+fn chown_op<P: AsRef<Path>>(_: P, _: Option<u32>, _: Option<u32>) -> Result<()> {
+    bail!("Changing file ownership is not supported on Windows.")
+}
+
+/// Sets the access and modification times (in seconds since epoch) of a file.
+fn utime_op<P: AsRef<Path>>(path: P, atime: f64, mtime: f64) -> Result<()> {
+    // Note: Timestamps before the epoch are rejected (on the JS side).
+    let times = FileTimes::new()
+        .set_accessed(UNIX_EPOCH + Duration::from_secs_f64(atime))
+        .set_modified(UNIX_EPOCH + Duration::from_secs_f64(mtime));
+
+    // Note: Windows requires write access to update a file's timestamps.
+    let file = match cfg!(target_family = "windows") {
+        true => OpenOptions::new().write(true).open(path)?,
+        false => File::open(path)?,
+    };
+
+    file.set_times(times).map_err(|e| anyhow!(e))
+}
+
 /// Creates a JavaScript file stats object.
 fn create_v8_stats_object<'a>(
     scope: &mut v8::HandleScope<'a>,
@@ -3,6 +3,7 @@ use crate::bindings::throw_type_error;
 use crate::errors::unwrap_or_exit;
 use crate::modules::load_import;
 use crate::modules::resolve_import;
+use crate::modules::validate_import_attributes;
 use crate::modules::EsModuleFuture;
 use crate::modules::ModuleGraph;
 use crate::modules::ModuleStatus;
@@ -58,10 +59,13 @@ pub extern "C" fn host_initialize_import_meta_object_cb(
     let state = JsRuntime::state(scope);
     let state = state.borrow();
 
-    // Make the module global.
-    let module = v8::Global::new(scope, module);
-
-    let url = state.module_map.get_path(module).unwrap();
+    // Note: Evicted modules (see the `max_cached_modules` option) are no longer
+    // in the module-map, but their code may still be running.
+    let url = state
+        .module_map
+        .get_path(v8::Global::new(scope, module))
+        .or_else(|| state.module_map.get_evicted_path(scope, module))
+        .unwrap();
     let is_main = state.module_map.main() == Some(url.to_owned());
 
     // Setup import.url property.
@@ -140,6 +144,10 @@ pub extern "C" fn promise_reject_cb(message: v8::PromiseRejectMessage) {
         }
         PromiseRejectAfterResolved | PromiseResolveAfterResolved => {}
     }
+
+    // Note: The overflowing rejections are only queued here, since hooks can't
+    // run (nor the process exit) in the middle of V8's rejection callback.
+    state.exceptions.flush_overflowing_rejections();
 }
 
 // Called when we require the embedder to load a module.
@@ -149,7 +157,7 @@ pub fn host_import_module_dynamically_cb<'s>(
     _: v8::Local<'s, v8::Data>,
     base: v8::Local<'s, v8::Value>,
     specifier: v8::Local<'s, v8::String>,
-    _: v8::Local<v8::FixedArray>,
+    attributes: v8::Local<v8::FixedArray>,
 ) -> Option<v8::Local<'s, v8::Promise>> {
     // Get module base and specifier as strings.
     let base = base.to_rust_string_lossy(scope);
@@ -176,6 +184,15 @@ pub fn host_import_module_dynamically_cb<'s>(
         }
     };
 
+    // Dynamic imports store the import attributes as (key, value) pairs.
+    if let Err(e) = validate_import_attributes(scope, &specifier, attributes, 2) {
+        drop(state);
+        let exception = v8::String::new(scope, &e.to_string()).unwrap();
+        let exception = v8::Exception::error(scope, exception);
+        promise_resolver.reject(scope, exception);
+        return Some(promise);
+    }
+
     let dynamic_import_being_fetched = state
         .module_map
         .pending
@@ -184,6 +201,8 @@ pub fn host_import_module_dynamically_cb<'s>(
 
     // Check if the requested dynamic module is already resolved.
     if state.module_map.index.contains_key(&specifier) && !dynamic_import_being_fetched {
+        state.module_map.touch(&specifier);
+
         // Create a local handle for the module.
         let module = state.module_map.get(&specifier).unwrap();
         let module = module.open(scope);
@@ -223,7 +242,8 @@ pub fn host_import_module_dynamically_cb<'s>(
 
     let task = {
         let specifier = specifier.clone();
-        move || match load_import(&specifier, true) {
+        let max_size = state.options.max_module_size;
+        move || match load_import(&specifier, true, max_size) {
             anyhow::Result::Ok(source) => Some(Ok(bincode::serialize(&source).unwrap())),
             Err(e) => Some(Result::Err(e)),
         }
@@ -1,8 +1,16 @@
+use crate::bindings::get_internal_ref;
 use crate::bindings::set_constant_to;
 use crate::bindings::set_exception_code;
 use crate::bindings::set_function_to;
+use crate::bindings::set_internal_ref;
+use crate::bindings::throw_exception;
+use crate::bindings::throw_type_error;
+use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Result;
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
 
 pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     // Create local JS object.
@@ -11,6 +19,10 @@ pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     set_function_to(scope, target, "parseRequest", parse_incoming_request);
     set_function_to(scope, target, "parseResponse", parse_incoming_response);
     set_function_to(scope, target, "parseChunks", parse_body_chunks);
+    set_function_to(scope, target, "createDecoder", create_decoder);
+    set_function_to(scope, target, "decode", decode);
+    set_function_to(scope, target, "finishDecoder", finish_decoder);
+    set_function_to(scope, target, "releaseDecoder", release_decoder);
 
     // Return v8 global handle.
     v8::Global::new(scope, target)
@@ -124,6 +136,9 @@ fn parse_incoming_response(
     let status_code = response.code.unwrap_or_default();
     let status_code = v8::Integer::new(scope, status_code as i32);
 
+    let version = response.version.unwrap_or_default();
+    let version = v8::Integer::new(scope, version as i32);
+
     let headers = response
         .headers
         .iter()
@@ -146,6 +161,7 @@ fn parse_incoming_response(
     let target = v8::Object::new(scope);
 
     set_constant_to(scope, target, "statusCode", status_code.into());
+    set_constant_to(scope, target, "version", version.into());
     set_constant_to(scope, target, "headers", headers.into());
     set_constant_to(scope, target, "marker", marker.into());
 
@@ -253,6 +269,257 @@ fn get_available_chunks(buffer: &mut Vec<u8>) -> Result<(Vec<Vec<u8>>, usize, bo
     Ok((chunks, cursor_position, received_last_chunk))
 }
 
+/// A buffer the decompressors write their output to.
+#[derive(Clone, Default)]
+struct Sink(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A streaming decompressor for an HTTP `Content-Encoding`.
+struct Decoder {
+    codec: Codec,
+    output: Sink,
+}
+
+enum Codec {
+    Gzip(flate2::write::GzDecoder<Sink>),
+    Deflate {
+        stream: flate2::Decompress,
+        // The bytes read before the zlib header got validated (replayed as a
+        // raw deflate stream when the header is missing).
+        header: Option<Vec<u8>>,
+        ended: bool,
+    },
+    Brotli(Box<brotli::DecompressorWriter<Sink>>),
+}
+
+impl Decoder {
+    /// Creates a decoder for the given encoding (if supported).
+    fn new(encoding: &str) -> Option<Self> {
+        let output = Sink::default();
+        let codec = match encoding {
+            "gzip" | "x-gzip" => Codec::Gzip(flate2::write::GzDecoder::new(output.clone())),
+            "deflate" => Codec::Deflate {
+                stream: flate2::Decompress::new(true),
+                header: Some(vec![]),
+                ended: false,
+            },
+            "br" => Codec::Brotli(Box::new(brotli::DecompressorWriter::new(
+                output.clone(),
+                4096,
+            ))),
+            _ => return None,
+        };
+
+        Some(Self { codec, output })
+    }
+
+    /// Decompresses the given bytes, returning whatever output is available.
+    fn decode(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        match &mut self.codec {
+            Codec::Gzip(writer) => {
+                writer.write_all(data)?;
+                writer.flush()?;
+            }
+            Codec::Brotli(writer) => {
+                writer.write_all(data)?;
+                writer.flush()?;
+            }
+            // Note: Anything after the end of the zlib stream is ignored.
+            Codec::Deflate { ended: true, .. } => {}
+            Codec::Deflate {
+                stream,
+                header,
+                ended,
+            } => {
+                let output = &mut self.output.0.borrow_mut();
+                if let Some(bytes) = header.as_mut() {
+                    bytes.extend_from_slice(data);
+                }
+
+                *ended = match inflate(stream, data, output) {
+                    Ok(ended) => ended,
+                    // Note: Some servers send raw deflate streams (without the
+                    // zlib wrapper) as the "deflate" encoding.
+                    Err(_) if header.is_some() && stream.total_out() == 0 => {
+                        *stream = flate2::Decompress::new(false);
+                        inflate(stream, &header.take().unwrap(), output)?
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                // The zlib header (two bytes) is valid once it's been read.
+                if stream.total_in() >= 2 {
+                    header.take();
+                }
+            }
+        }
+        Ok(self.output.0.take())
+    }
+
+    /// Flushes the remaining output, failing if the stream was truncated
+    /// or its checksum doesn't match.
+    fn finish(&mut self) -> Result<Vec<u8>> {
+        let result = match &mut self.codec {
+            Codec::Gzip(writer) => writer.try_finish(),
+            Codec::Brotli(writer) => writer.close(),
+            Codec::Deflate { ended: true, .. } => Ok(()),
+            Codec::Deflate { ended: false, .. } => Err(std::io::ErrorKind::UnexpectedEof.into()),
+        };
+
+        match result {
+            Ok(()) => Ok(self.output.0.take()),
+            Err(e) => bail!("The compressed body is incomplete or corrupt ({e})."),
+        }
+    }
+}
+
+/// Inflates a zlib stream chunk, returning whether the end of the stream was reached.
+fn inflate(
+    stream: &mut flate2::Decompress,
+    mut input: &[u8],
+    output: &mut Vec<u8>,
+) -> Result<bool> {
+    loop {
+        output.reserve(32 * 1024);
+        let consumed = stream.total_in();
+        let status = stream.decompress_vec(input, output, flate2::FlushDecompress::None)?;
+        input = &input[(stream.total_in() - consumed) as usize..];
+
+        match status {
+            flate2::Status::StreamEnd => return Ok(true),
+            _ if input.is_empty() && output.len() < output.capacity() => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+/// Creates a streaming decompressor for the given content-encoding.
+fn create_decoder(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let encoding = args.get(0).to_rust_string_lossy(scope).to_lowercase();
+    let decoder = match Decoder::new(encoding.trim()) {
+        Some(decoder) => decoder,
+        None => {
+            let err = anyhow!("Content-Encoding \"{encoding}\" not supported.");
+            throw_exception(scope, &err);
+            return;
+        }
+    };
+
+    let decoder_wrapper = v8::ObjectTemplate::new(scope);
+
+    // Allocate space for the wrapped Rust type.
+    decoder_wrapper.set_internal_field_count(1);
+
+    let decoder_wrapper = decoder_wrapper.new_instance(scope).unwrap();
+    set_internal_ref(scope, decoder_wrapper, 0, Some(decoder));
+
+    rv.set(decoder_wrapper.into());
+}
+
+/// Decompresses a body chunk.
+fn decode(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let decoder_wrapper = match v8::Local::<v8::Object>::try_from(args.get(0)) {
+        Ok(wrapper) if wrapper.internal_field_count() == 1 => wrapper,
+        _ => {
+            throw_type_error(scope, "The first argument must be a decoder.");
+            return;
+        }
+    };
+
+    let buffer = match v8::Local::<v8::ArrayBufferView>::try_from(args.get(1)) {
+        Ok(buffer) => buffer,
+        Err(_) => {
+            throw_type_error(scope, "The chunk must be a TypedArray or a DataView.");
+            return;
+        }
+    };
+
+    let decoder = get_internal_ref::<Option<Decoder>>(scope, decoder_wrapper, 0);
+    let mut data = vec![0; buffer.byte_length()];
+    buffer.copy_contents(&mut data);
+
+    let result = match decoder.as_mut() {
+        Some(decoder) => decoder.decode(&data),
+        None => Err(anyhow!("The decoder has already been released.")),
+    };
+
+    match result {
+        Ok(output) => rv.set(to_array_buffer(scope, output).into()),
+        Err(e) => {
+            decoder.take();
+            throw_exception(scope, &e);
+        }
+    }
+}
+
+/// Flushes a decoder once the whole body has been received, and releases it.
+fn finish_decoder(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let decoder_wrapper = match v8::Local::<v8::Object>::try_from(args.get(0)) {
+        Ok(wrapper) if wrapper.internal_field_count() == 1 => wrapper,
+        _ => {
+            throw_type_error(scope, "The first argument must be a decoder.");
+            return;
+        }
+    };
+
+    let decoder = get_internal_ref::<Option<Decoder>>(scope, decoder_wrapper, 0);
+    let result = match decoder.take() {
+        Some(mut decoder) => decoder.finish(),
+        None => Err(anyhow!("The decoder has already been released.")),
+    };
+
+    match result {
+        Ok(output) => rv.set(to_array_buffer(scope, output).into()),
+        Err(e) => throw_exception(scope, &e),
+    }
+}
+
+// Moves the decompressed bytes into a new ArrayBuffer.
+fn to_array_buffer<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    data: Vec<u8>,
+) -> v8::Local<'a, v8::ArrayBuffer> {
+    let store = data.into_boxed_slice();
+    let store = v8::ArrayBuffer::new_backing_store_from_boxed_slice(store).make_shared();
+    v8::ArrayBuffer::with_backing_store(scope, &store)
+}
+
+/// Drops a decoder whose body won't be read any further.
+fn release_decoder(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+) {
+    match v8::Local::<v8::Object>::try_from(args.get(0)) {
+        Ok(wrapper) if wrapper.internal_field_count() == 1 => {
+            get_internal_ref::<Option<Decoder>>(scope, wrapper, 0).take();
+        }
+        _ => throw_type_error(scope, "The first argument must be a decoder."),
+    }
+}
+
 /* --------------------------------------------------------------------------------------*/
 // The following code is copied from the `streaming_httparse` crate.                      /
 // A fast and simple to use HTTP-Parsing crate (https://crates.io/crates/stream-httparse) /
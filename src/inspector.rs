@@ -5,6 +5,8 @@
 
 use crate::errors::generic_error;
 use crate::errors::unwrap_or_exit;
+use anyhow::bail;
+use anyhow::Result;
 use axum::extract::ws::Message;
 use axum::extract::ws::WebSocket;
 use axum::extract::ws::WebSocketUpgrade;
@@ -17,6 +19,8 @@ use dune_event_loop::LoopInterruptHandle;
 use futures::sink::SinkExt;
 use futures::stream::StreamExt;
 use serde::Serialize;
+use serde_json::json;
+use serde_json::Value;
 use std::cell::RefCell;
 use std::mem::MaybeUninit;
 use std::net::SocketAddrV4;
@@ -257,6 +261,11 @@ impl JsRuntimeInspector {
             session.break_on_next_statement();
         }
     }
+
+    /// Connects an in-process session (no devtools frontend involved).
+    pub fn connect_local_session(&self) -> Box<LocalInspectorSession> {
+        LocalInspectorSession::new(self.v8_inspector.clone())
+    }
 }
 
 impl v8::inspector::V8InspectorClientImpl for JsRuntimeInspector {
@@ -385,6 +394,100 @@ impl v8::inspector::ChannelImpl for InspectorSession {
     fn flush_protocol_notifications(&mut self) {}
 }
 
+/// An inspector session used by the runtime itself (e.g. for collecting code
+/// coverage), where CDP commands are dispatched and answered synchronously.
+pub struct LocalInspectorSession {
+    v8_channel: v8::inspector::ChannelBase,
+    v8_session: v8::UniqueRef<v8::inspector::V8InspectorSession>,
+    responses: Vec<Value>,
+    next_id: u64,
+}
+
+impl LocalInspectorSession {
+    fn new(
+        v8_inspector: Rc<RefCell<v8::UniquePtr<v8::inspector::V8Inspector>>>,
+    ) -> Box<LocalInspectorSession> {
+        new_box_with(move |self_ptr| {
+            let v8_channel = v8::inspector::ChannelBase::new::<Self>();
+            let mut v8_inspector = v8_inspector.borrow_mut();
+            let v8_inspector_ptr = v8_inspector.as_mut().unwrap();
+
+            #[allow(clippy::undocumented_unsafe_blocks)]
+            let v8_session = v8_inspector_ptr.connect(
+                CONTEXT_GROUP_ID,
+                unsafe { &mut *self_ptr },
+                v8::inspector::StringView::empty(),
+                v8::inspector::V8InspectorClientTrustLevel::FullyTrusted,
+            );
+
+            Self {
+                v8_channel,
+                v8_session,
+                responses: Vec::new(),
+                next_id: 0,
+            }
+        })
+    }
+
+    /// Sends a CDP command to V8 and returns its result.
+    pub fn post(&mut self, method: &str, params: Value) -> Result<Value> {
+        self.next_id += 1;
+        let id = self.next_id;
+
+        let message = json!({ "id": id, "method": method, "params": params }).to_string();
+        let v8_message = v8::inspector::StringView::from(message.as_bytes());
+        self.v8_session.dispatch_protocol_message(v8_message);
+
+        // Note: The response is sent back before `dispatch_protocol_message` returns.
+        let position = self.responses.iter().position(|r| r["id"] == id);
+        let mut response = match position {
+            Some(index) => self.responses.remove(index),
+            None => bail!("No response received for the \"{method}\" command."),
+        };
+
+        if let Some(error) = response.get("error") {
+            let message = error["message"].as_str().unwrap_or_default();
+            bail!("{message} ({method})");
+        }
+
+        Ok(response["result"].take())
+    }
+}
+
+impl v8::inspector::ChannelImpl for LocalInspectorSession {
+    fn base(&self) -> &v8::inspector::ChannelBase {
+        &self.v8_channel
+    }
+
+    fn base_mut(&mut self) -> &mut v8::inspector::ChannelBase {
+        &mut self.v8_channel
+    }
+
+    unsafe fn base_ptr(this: *const Self) -> *const v8::inspector::ChannelBase
+    where
+        Self: Sized,
+    {
+        // SAFETY: This pointer is valid for the whole lifetime of the session.
+        unsafe { std::ptr::addr_of!((*this).v8_channel) }
+    }
+
+    fn send_response(
+        &mut self,
+        _call_id: i32,
+        message: v8::UniquePtr<v8::inspector::StringBuffer>,
+    ) {
+        let message = message.unwrap().string().to_string();
+        if let Ok(response) = serde_json::from_str(&message) {
+            self.responses.push(response);
+        }
+    }
+
+    // Notifications (e.g. console events) are of no use to local sessions.
+    fn send_notification(&mut self, _: v8::UniquePtr<v8::inspector::StringBuffer>) {}
+
+    fn flush_protocol_notifications(&mut self) {}
+}
+
 #[derive(Clone)]
 struct AppState {
     pub id: Uuid,
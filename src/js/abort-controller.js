@@ -5,7 +5,7 @@
 //
 // https://developer.mozilla.org/en-US/docs/Web/API/AbortController
 
-import { EventEmitter } from 'events';
+import { EventTarget } from '@web/event_target';
 
 /**
  * Error type referring to an operation being aborted.
@@ -22,14 +22,14 @@ class AbortError extends Error {
  * The `AbortSignal` interface represents a signal object that allows you
  * to communicate with a request and abort it.
  */
-export class AbortSignal {
+export class AbortSignal extends EventTarget {
   /**
    * Creates a new abort-signal instance.
    *
    * @returns {AbortSignal}
    */
   constructor() {
-    this.eventEmitter = new EventEmitter();
+    super();
     this.onabort = null;
     this.aborted = false;
     this.reason = undefined;
@@ -64,20 +64,17 @@ export class AbortSignal {
     throw new Error('Not implemented!');
   }
 
-  addEventListener(name, handler) {
-    this.eventEmitter.on(name, handler);
-  }
-
-  removeEventListener(name, handler) {
-    this.eventEmitter.removeListener(name, handler);
-  }
-
-  dispatchEvent(type) {
-    const event = { type, target: this };
-    const handlerName = `on${type}`;
-
-    if (typeof this[handlerName] === 'function') this[handlerName](event);
-    this.eventEmitter.emit(type, event);
+  /**
+   * Registers an event listener (e.g. for the `abort` event).
+   *
+   * @param {String} type - The event type to listen for.
+   * @param {Function|Object} handler - A function, or an object with a `handleEvent` method.
+   * @param {Object} [options] - Listener options (e.g. `once`).
+   */
+  addEventListener(type, handler, options = {}) {
+    // Listeners added after the signal was aborted are never called.
+    if (type === 'abort' && this.aborted) return;
+    super.addEventListener(type, handler, options);
   }
 
   /**
@@ -111,6 +108,8 @@ export class AbortController {
     // If it's already aborted, don't do anything.
     if (this.signal.aborted) return;
 
+    // Note: The state is updated before any handler runs, so the handlers can
+    // already inspect the `aborted` and `reason` properties.
     this.signal.aborted = true;
     this.signal.reason = new AbortError(reason);
     this.signal.dispatchEvent('abort');
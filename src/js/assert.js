@@ -8,8 +8,21 @@
  * @module Assert
  */
 
+import fs from 'fs';
+import { inspect } from 'console';
+
 const VALUE_NAME_REGEXP = /\${(.*?)}/g;
 
+/**
+ * Keeps track of the assertions performed (used by the test runner).
+ */
+export const counter = { assertions: 0 };
+
+/**
+ * Snapshot assertion settings (`update` is enabled by `dune test --update`).
+ */
+export const snapshots = { update: false };
+
 class MessageFactory {
   /**
    * @param {string} template
@@ -687,6 +700,293 @@ class Assert {
       'Expected from callback to throw an Error "${expected}" but it didn\'t.'
     );
   }
+
+  /**
+   * Asserts that a value matches its stored snapshot (stored on first run).
+   *
+   * @param {object} context - The test context, providing the test's `name` and `file`.
+   * @param {*} value - The value to be compared against the snapshot.
+   */
+  static snapshot(context, value) {
+    const { name, file } = context || {};
+    if (typeof name !== 'string' || typeof file !== 'string') {
+      throw new TypeError(
+        'The "context" argument must provide the test "name" and "file".'
+      );
+    }
+
+    const { dir, path } = snapshotLocation(file);
+    const actual = inspect(value, { colors: false });
+
+    // Tests can take multiple snapshots, so they are numbered in order.
+    const count = (snapshotCounts.get(context) || 0) + 1;
+    snapshotCounts.set(context, count);
+
+    const key = `${name} ${count}`;
+    const stored = readSnapshots(path);
+
+    // Store the snapshot when it's new or when updating is requested.
+    if (!(key in stored) || snapshots.update) {
+      stored[key] = actual;
+      fs.mkdirSync(dir, { recursive: true });
+      fs.writeFileSync(path, JSON.stringify(stored, null, 2) + '\n');
+      return;
+    }
+
+    if (stored[key] !== actual) {
+      throw new Error(
+        `Snapshot "${key}" does not match:\n\n${diffLines(stored[key], actual)}`
+      );
+    }
+  }
+}
+
+// Numbers the snapshots taken by each test (every run gets a new context).
+const snapshotCounts = new WeakMap();
+
+// Returns where the snapshots of a test module are stored.
+function snapshotLocation(file) {
+  const path = file.replace(/^file:\/\//, '');
+  const index = path.lastIndexOf('/');
+  const dir = `${index > 0 ? path.slice(0, index) : '.'}/__snapshots__`;
+  return { dir, path: `${dir}/${path.slice(index + 1)}.snap` };
+}
+
+// Reads the stored snapshots of a test module (if any).
+function readSnapshots(path) {
+  try {
+    return JSON.parse(fs.readFileSync(path, { encoding: 'utf-8' }));
+  } catch (err) {
+    if (err.code === 'ENOENT') return {};
+    throw err;
+  }
+}
+
+// The original sources of the test files updated through inline snapshots,
+// along with the literals to write at each call site (by source offset).
+const inlineEdits = new Map();
+
+// Returns the location of the first stack frame (as a path, line and column).
+function callSite(stack) {
+  const frame = String(stack)
+    .split('\n')
+    .slice(1)
+    .map((line) => line.match(/at (?:.* \()?(.*?):(\d+):(\d+)\)?$/))
+    .find(Boolean);
+
+  if (!frame) throw new Error('Unable to locate the inline snapshot call.');
+
+  const [, path, line, column] = frame;
+  return {
+    path: path.replace(/^file:\/\//, ''),
+    line: Number(line),
+    column: Number(column),
+  };
+}
+
+// Finds the arguments of the call starting at `offset`, returning where the
+// first one ends, and the range of the second one (if present).
+function callArguments(source, offset) {
+  const commas = [];
+  const stack = [];
+  let i = source.indexOf('(', offset) + 1;
+
+  // Moves to the end of the next `token` (or of the source).
+  const skipTo = (token) => {
+    const end = source.indexOf(token, i);
+    i = end === -1 ? source.length : end + token.length - 1;
+  };
+
+  // Moves to the end of a string (template literals stop at expressions).
+  const skipString = (quote) => {
+    for (i++; i < source.length; i++) {
+      if (source[i] === '\\') i++;
+      else if (source[i] === quote) return;
+      else if (quote === '`' && source.startsWith('${', i)) {
+        stack.push('${');
+        i++;
+        return;
+      }
+    }
+  };
+
+  for (; i < source.length; i++) {
+    const char = source[i];
+
+    if (source.startsWith('//', i)) skipTo('\n');
+    else if (source.startsWith('/*', i)) skipTo('*/');
+    else if (char === "'" || char === '"' || char === '`') skipString(char);
+    else if (char === '}' && stack.at(-1) === '${') {
+      stack.pop();
+      skipString('`');
+    } else if ('([{'.includes(char)) stack.push(char);
+    else if (')]}'.includes(char) && stack.length > 0) stack.pop();
+    else if (char === ',' && stack.length === 0) commas.push(i);
+    else if (char === ')') {
+      const [first, second = i] = commas;
+      const hasSecond =
+        first !== undefined && source.slice(first + 1, second).trim() !== '';
+
+      return {
+        firstEnd: first ?? i,
+        second: hasSecond ? { start: first + 1, end: second } : null,
+      };
+    }
+  }
+
+  throw new Error('Unable to parse the inline snapshot call.');
+}
+
+// Writes the literal into the `expected` argument of the call at `offset`.
+function writeInlineSnapshot(source, offset, literal) {
+  const { firstEnd, second } = callArguments(source, offset);
+  return second
+    ? `${source.slice(0, second.start)} ${literal}${source.slice(second.end)}`
+    : `${source.slice(0, firstEnd).trimEnd()}, ${literal}` +
+        source.slice(firstEnd);
+}
+
+// Writes the serialized value into the `expected` argument of the call site.
+function updateInlineSnapshot(site, actual) {
+  const source = fs.readFileSync(site.path, { encoding: 'utf-8' });
+  let file = inlineEdits.get(site.path);
+
+  // Note: Files changed in the meantime (e.g. rewritten) are tracked anew.
+  if (file?.output !== source) {
+    file = { source, output: source, literals: new Map() };
+    inlineEdits.set(site.path, file);
+  }
+
+  const offset =
+    file.source
+      .split('\n')
+      .slice(0, site.line - 1)
+      .reduce((total, line) => total + line.length + 1, 0) +
+    (site.column - 1);
+
+  // Note: The value is written as a template literal (keeping its lines).
+  const escaped = actual
+    .replace(/\\/g, '\\\\')
+    .replace(/`/g, '\\`')
+    .replace(/\$\{/g, '\\${');
+  file.literals.set(offset, `\`${escaped}\``);
+
+  // Note: The call sites are located in the original source, so the edits are
+  // applied right-to-left (keeping the offsets of the remaining ones valid,
+  // even for several snapshots on the same line).
+  const updated = [...file.literals]
+    .sort(([a], [b]) => b - a)
+    .reduce(
+      (updated, [offset, literal]) =>
+        writeInlineSnapshot(updated, offset, literal),
+      file.source
+    );
+
+  if (updated === file.output) return;
+
+  file.output = updated;
+  fs.writeFileSync(site.path, updated);
+}
+
+// Creates a line based diff, marking removed (-) and added (+) lines.
+function diffLines(expected, actual) {
+  const a = expected.split('\n');
+  const b = actual.split('\n');
+
+  // Skip the common lines at the start and the end.
+  let start = 0;
+  while (start < a.length && start < b.length && a[start] === b[start]) {
+    start++;
+  }
+
+  let endA = a.length;
+  let endB = b.length;
+  while (endA > start && endB > start && a[endA - 1] === b[endB - 1]) {
+    endA--;
+    endB--;
+  }
+
+  return [
+    ...a.slice(0, start).map((line) => `  ${line}`),
+    ...a.slice(start, endA).map((line) => `- ${line}`),
+    ...b.slice(start, endB).map((line) => `+ ${line}`),
+    ...a.slice(endA).map((line) => `  ${line}`),
+  ].join('\n');
 }
 
-export default Assert;
+/**
+ * The assertions exposed to the user-land are also being counted.
+ *
+ * Note: Core modules should use the `Assert` class directly, so their internal
+ * checks will not affect the user's assertion count.
+ */
+class CountedAssert extends Assert {}
+
+// Assertions are using other assertions internally, so only the
+// top-level calls are being counted.
+let nesting = 0;
+
+for (const name of Object.getOwnPropertyNames(Assert)) {
+  const assertion = Assert[name];
+  if (typeof assertion !== 'function') continue;
+
+  CountedAssert[name] = function (...args) {
+    if (nesting === 0) counter.assertions++;
+    nesting++;
+    try {
+      return assertion.apply(this, args);
+    } finally {
+      nesting--;
+    }
+  };
+}
+
+/**
+ * Asserts that a value matches its stored snapshot (stored on first run).
+ *
+ * @param {object} context - The test context, providing the test's `name` and `file`.
+ * @param {*} value - The value to be compared against the snapshot.
+ */
+export function assertSnapshot(context, value) {
+  CountedAssert.snapshot(context, value);
+}
+
+/**
+ * Asserts that a value matches the snapshot written inline, as the `expected`
+ * argument. Running with `dune test --update` fills it in (editing the test
+ * file), so the argument can be left out the first time.
+ *
+ * Note: The call site is found through the stack trace, so only JavaScript
+ * test files can be updated (the positions of transpiled files differ).
+ *
+ * @param {*} value - The value to be compared against the snapshot.
+ * @param {string} [expected] - The serialized value of the snapshot.
+ */
+export function assertInlineSnapshot(value, expected) {
+  counter.assertions++;
+  const actual = inspect(value, { colors: false });
+
+  if (snapshots.update) {
+    // Note: The stack is captured here, so the top frame is the call site.
+    const holder = {};
+    Error.captureStackTrace(holder, assertInlineSnapshot);
+    updateInlineSnapshot(callSite(holder.stack), actual);
+    return;
+  }
+
+  if (expected === undefined) {
+    throw new Error(
+      'Inline snapshot is missing (run `dune test --update` to fill it in).'
+    );
+  }
+
+  if (expected !== actual) {
+    throw new Error(
+      `Inline snapshot does not match:\n\n${diffLines(expected, actual)}`
+    );
+  }
+}
+
+export { Assert };
+
+export default CountedAssert;
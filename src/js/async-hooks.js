@@ -0,0 +1,53 @@
+// Async Context Tracking
+//
+// This module provides a minimal implementation of the `AsyncLocalStorage` API
+// https://nodejs.org/api/async_context.html#class-asynclocalstorage
+
+'use strict';
+
+import { getContextValue, runWithContextValue } from 'util';
+
+/**
+ * Stores a value that follows the code across timers, microtasks, promise
+ * continuations and async operations started within `run`.
+ */
+export class AsyncLocalStorage {
+  // Note: Every instance keeps its store under its own key, so nested (or
+  // unrelated) storages never overwrite each other.
+  #key = Symbol('AsyncLocalStorage');
+
+  /**
+   * Runs a function with the given store, which is returned by `getStore`
+   * from that function and any async work it schedules.
+   *
+   * @param {*} store - The store to run the function with.
+   * @param {Function} fn - The function to run.
+   * @param {...*} [args] - Arguments passed to the function.
+   * @returns {*} The function's return value.
+   */
+  run(store, fn, ...args) {
+    return runWithContextValue(this.#key, store, fn, ...args);
+  }
+
+  /**
+   * Runs a function outside of any store (`getStore` returns `undefined`).
+   *
+   * @param {Function} fn - The function to run.
+   * @param {...*} [args] - Arguments passed to the function.
+   * @returns {*} The function's return value.
+   */
+  exit(fn, ...args) {
+    return runWithContextValue(this.#key, undefined, fn, ...args);
+  }
+
+  /**
+   * Returns the current store (or `undefined` outside of `run`).
+   *
+   * @returns {*}
+   */
+  getStore() {
+    return getContextValue(this.#key);
+  }
+}
+
+export default { AsyncLocalStorage };
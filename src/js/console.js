@@ -14,7 +14,7 @@
 import { performance } from 'perf_hooks';
 import { green, yellow, cyan, red, bright_black } from 'colors';
 
-const { callConsole } = process.binding('stdio');
+const { callConsole, consoleSize } = process.binding('stdio');
 
 // Returns a string with as many spaces as the parameter specified.
 function pre(amount) {
@@ -26,21 +26,104 @@ function objectToString(value) {
   return Object.prototype.toString.call(value);
 }
 
+// Reads a property without invoking the accessors found along the prototype
+// chain (e.g. throwing getters of host objects), returning `accessor` for them.
+function safeProperty(object, key, accessor = undefined) {
+  let target = object;
+  while (target !== null && target !== undefined) {
+    const descriptor = Object.getOwnPropertyDescriptor(target, key);
+    if (descriptor) return 'value' in descriptor ? descriptor.value : accessor;
+    target = Object.getPrototypeOf(target);
+  }
+  return undefined;
+}
+
+// Returns the name of the object's constructor (if it can be safely read).
+function constructorName(object) {
+  const constructor = safeProperty(object, 'constructor');
+  if (typeof constructor !== 'function') return undefined;
+  return safeProperty(constructor, 'name');
+}
+
+// Calls a built-in getter (e.g. `DataView.prototype.byteLength`) directly, so
+// overrides on the prototype chain of subclasses are never invoked.
+function intrinsic(prototype, key, object) {
+  return Object.getOwnPropertyDescriptor(prototype, key).get.call(object);
+}
+
+// Removes the ANSI color characters from a string.
+function stripColors(value) {
+  return value.replace(/\u001b\[[0-9;]*m/g, '');
+}
+
+// Returns the length of a string as displayed (ignoring the color characters).
+function visibleLength(value) {
+  return stripColors(value).length;
+}
+
+// Default options used when stringifying values.
+const defaultOptions = {
+  colors: true,
+  iterableLimit: 100,
+  numericSeparator: false,
+  getters: false,
+  sorted: false,
+  strAbbreviateSize: 100,
+  depth: 4,
+  breakLength: 80,
+  compact: 3,
+  showProxy: false,
+  showHidden: false,
+};
+
+// Creates the context that is passed down while stringifying a value.
+function createContext(options = {}) {
+  const state = { seen: [], circular: new Map(), deepest: 0 };
+  return { ...defaultOptions, ...options, ...state };
+}
+
+// Creates the context used by the console methods (breaking lines at the
+// width of the terminal, when available).
+function createConsoleContext() {
+  return createContext({ breakLength: consoleSize()?.columns ?? 80 });
+}
+
+// Checks if a list of entries fits in a single line (at the given depth).
+//
+// Note: With a numeric `compact` option, only objects with less than that many
+// levels of (non-empty) nested objects are combined on a single line.
+// The `breakLength` option then decides whether they fit on it (so passing
+// `Infinity` keeps everything on a single line).
+function fitsInline(entries, ctx, depth, prefix = '') {
+  if (entries.length === 0) return true;
+
+  const levels = ctx.deepest > depth ? ctx.deepest - depth : 0;
+  ctx.deepest = Math.max(ctx.deepest, depth);
+
+  if (ctx.compact === false) return false;
+  if (ctx.compact !== true && levels >= ctx.compact) return false;
+  if (entries.some((entry) => entry.includes('\n'))) return false;
+
+  const inline = `${prefix}{ ${entries.join(', ')} }`;
+  return (depth - 1) * 2 + visibleLength(inline) <= ctx.breakLength;
+}
+
 /**
  * Stringifies almost all JavaScript built-in types.
  *
  * @ignore
  * @param {*} value
- * @param {WeakSet} seen
+ * @param {Object} ctx
  * @param {number} depth
  * @returns {string}
  */
 
-function stringify(value, seen, depth = 0) {
+function stringify(value, ctx = createContext(), depth = 0) {
   switch (typeof value) {
     case 'string':
-      return depth > 0 ? stringifyText(value) : value;
+      return depth > 0 ? stringifyText(value, ctx) : value;
     case 'number':
+      return yellow(stringifyNumber(value, ctx));
     case 'boolean':
       return yellow(String(value));
     case 'undefined':
@@ -48,101 +131,196 @@ function stringify(value, seen, depth = 0) {
     case 'symbol':
       return green(String(value));
     case 'bigint':
-      return yellow(String(value) + 'n');
+      return yellow(stringifyNumber(value, ctx) + 'n');
     case 'object':
-      return !value ? 'null' : stringifyObject(value, seen, ++depth);
+      return !value ? 'null' : stringifyReference(value, ctx, ++depth);
     case 'function':
-      return !value.name
-        ? cyan('[Function (anonymous)]')
-        : cyan(`[Function: ${value.name}]`);
+      return stringifyReference(value, ctx, ++depth);
     default:
       return '[Unknown]';
   }
 }
 
-function stringifyText(value) {
-  const text = value.length > 100 ? `${value.slice(0, 100)}...` : value;
-  const textEscaped = JSON.stringify(text);
-  return green(textEscaped);
+// Groups the digits of a numeric string with underscores (e.g. 1_000_000).
+function groupDigits(digits, fromStart = false) {
+  const groups = [];
+  if (fromStart) {
+    for (let i = 0; i < digits.length; i += 3) {
+      groups.push(digits.slice(i, i + 3));
+    }
+  } else {
+    for (let i = digits.length; i > 0; i -= 3) {
+      groups.unshift(digits.slice(Math.max(i - 3, 0), i));
+    }
+  }
+  return groups.join('_');
+}
+
+function stringifyNumber(value, ctx) {
+  // Note: `String(-0)` returns "0" so we have to handle it explicitly.
+  if (Object.is(value, -0)) return '-0';
+
+  const output = String(value);
+
+  // Numbers in exponential notation, NaN and Infinity are printed as is.
+  if (!ctx.numericSeparator || !/^-?\d+(\.\d+)?$/.test(output)) {
+    return output;
+  }
+
+  const sign = output.startsWith('-') ? '-' : '';
+  const [integer, fraction] = output.replace('-', '').split('.');
+  const grouped = groupDigits(integer);
+
+  return fraction
+    ? `${sign}${grouped}.${groupDigits(fraction, true)}`
+    : `${sign}${grouped}`;
+}
+
+// Escape sequences for the special characters in strings.
+const escapeSequences = {
+  '\\': '\\\\',
+  '\n': '\\n',
+  '\t': '\\t',
+  '\r': '\\r',
+  '\b': '\\b',
+  '\f': '\\f',
+};
+
+// Wraps a string in quotes (single quotes are preferred) escaping
+// the control characters and the quote in use.
+function quoteText(value) {
+  let quote = "'";
+  if (value.includes("'")) {
+    if (!value.includes('"')) quote = '"';
+    else if (!value.includes('`') && !value.includes('${')) quote = '`';
+  }
+
+  const special = new RegExp(`[\\x00-\\x1f\\x7f\\\\${quote}]`, 'g');
+  const escaped = value.replace(special, (char) => {
+    if (char === quote) return `\\${quote}`;
+    if (escapeSequences[char]) return escapeSequences[char];
+    const code = char.charCodeAt(0).toString(16).toUpperCase();
+    return `\\x${code.padStart(2, '0')}`;
+  });
+
+  return `${quote}${escaped}${quote}`;
+}
+
+function stringifyText(value, ctx) {
+  const limit = ctx.strAbbreviateSize;
+  if (value.length <= limit) return green(quoteText(value));
+
+  // Show how many characters were left out.
+  const remaining = value.length - limit;
+  const text = green(quoteText(value.slice(0, limit)));
+  return `${text}... ${remaining} more characters`;
 }
 
 function isArray(value) {
   return Array.isArray(value);
 }
 
-function stringifyArray(arr, seen, depth) {
+function isArguments(value) {
+  return objectToString(value) === '[object Arguments]';
+}
+
+// Describes a run of holes in a sparse array (e.g. `<2 empty items>`).
+function emptyItems(count) {
+  return bright_black(`<${count} empty item${count > 1 ? 's' : ''}>`);
+}
+
+// Describes how many members of a collection were left out, because of the
+// `iterableLimit` option (e.g. `... 3 more items`).
+function moreMembers(count, [singular, plural] = ['item', 'items']) {
+  return `... ${count} more ${count === 1 ? singular : plural}`;
+}
+
+// Appends the truncation marker (if any) to a list of lines.
+function appendMarker(output, marker, depth) {
+  return marker ? `${output},\n${pre(depth * 2)}${marker}` : output;
+}
+
+function stringifyArray(arr, ctx, depth) {
   // Special formatting required if array has only numbers.
-  const hasOnlyNumbers = arr.every((elem) => typeof elem === 'number');
+  const hasOnlyNumbers = Array.prototype.every.call(
+    arr,
+    (elem) => typeof elem === 'number'
+  );
 
+  // Note: Walking the (ascending) index keys, instead of every index, keeps
+  // huge sparse arrays (e.g. `new Array(1e9)`) cheap to inspect.
   const entries = [];
-  for (const elem of arr) {
-    entries.push(stringify(elem, seen, depth));
+  let next = 0;
+
+  for (const key of Object.keys(arr)) {
+    const index = Number(key);
+    if (!(index < arr.length) || String(index) !== key) break;
+    if (entries.length >= ctx.iterableLimit) break;
+
+    if (index > next) entries.push(emptyItems(index - next));
+    entries.push(stringify(arr[index], ctx, depth));
+    next = index + 1;
+  }
+
+  if (arr.length > next && entries.length < ctx.iterableLimit) {
+    entries.push(emptyItems(arr.length - next));
+    next = arr.length;
   }
 
-  // Remove the color characters to get the proper length.
-  const uncoloredEntries = entries.join('').replace(/\u001b\[[0-9;]*m/g, '');
+  const marker = arr.length > next ? moreMembers(arr.length - next) : '';
 
   // Multiline formatting.
-  if (uncoloredEntries.length > 60) {
+  if (!fitsInline(marker ? [...entries, marker] : entries, ctx, depth)) {
     const start = '[\n';
     const end = `\n${pre((depth - 1) * 2)}]`;
-    const entriesPretty = prettifyArray(entries, depth, hasOnlyNumbers);
-    return `${start}${entriesPretty}${end}`;
+    const entriesPretty = prettifyArray(entries, ctx, depth, hasOnlyNumbers);
+    return `${start}${appendMarker(entriesPretty, marker, depth)}${end}`;
   }
 
   // Inline formatting.
+  if (marker) entries.push(marker);
   return entries.length > 0 ? `[ ${entries.join(', ')} ]` : `[]`;
 }
 
+// Note: Covers every typed array kind (e.g. `BigInt64Array`).
 function isTypedArray(value) {
-  switch (Object.prototype.toString.call(value)) {
-    case '[object Int8Array]':
-    case '[object Uint8Array]':
-    case '[object Uint8ClampedArray]':
-    case '[object Uint16Array]':
-    case '[object Int32Array]':
-    case '[object Uint32Array]':
-    case '[object Float32Array]':
-    case '[object Float64Array]':
-      return true;
-    default:
-      return false;
-  }
+  return ArrayBuffer.isView(value) && !isDataView(value);
 }
 
 // Calculate the grid size (trying to make perfect squares and minimizing empty space).
-// 1. Max out at 12xN.
+// 1. Max out at 12xN (or as many columns as fit in the `breakLength`).
 // 2. Max out at 01xN (if the lengthier element is too big).
-function getMaxElementsPerRow(arr, avgElementLength, maxElementLength) {
+function getMaxElementsPerRow(arr, avgElementLength, maxElementLength, width) {
   if (maxElementLength > 30) return 1;
+  // Note: Every column takes two extra characters for the separator.
+  const columns = Math.floor(width / (maxElementLength + 2));
   return Math.min(
     Math.max(
       Math.floor((Math.sqrt(arr.length) * avgElementLength) / maxElementLength),
       1
     ),
-    12
+    Math.max(Math.min(columns, 12), 1)
   );
 }
 
-function prettifyArray(arr, depth = 0, hasOnlyNumbers) {
-  // Remove the color characters so we can calculate the AVG and MAX correctly.
-  const uncolored = arr.map(
-    (elem) => elem.replace(/\u001b\[[0-9;]*m/g, '').length
-  );
+function prettifyArray(arr, ctx, depth = 0, hasOnlyNumbers) {
+  // Measure the visible lengths so we can calculate the AVG and MAX correctly.
+  const lengths = arr.map(visibleLength);
 
-  const maxElementLength = Math.max(...uncolored);
-  const avgElementLength = uncolored.reduce((a, b) => a + b) / uncolored.length;
+  const maxElementLength = Math.max(...lengths);
+  const avgElementLength = lengths.reduce((a, b) => a + b) / lengths.length;
 
   // Calculate the grid size.
   const maxElementsPerRow = getMaxElementsPerRow(
     arr,
     avgElementLength,
-    maxElementLength
+    maxElementLength,
+    ctx.breakLength - depth * 2
   );
 
   // Tries to align the columns.
   const alignColumn = (elem, i) => {
-    const length = elem.replace(/\u001b\[[0-9;]*m/g, '').length;
+    const length = visibleLength(elem);
     const shift = maxElementsPerRow === 1 ? 0 : maxElementLength - length;
     if (hasOnlyNumbers) {
       return i === arr.length - 1
@@ -178,33 +356,79 @@ function prettifyArray(arr, depth = 0, hasOnlyNumbers) {
   return output;
 }
 
-function stringifyTypedArray(arr, depth = 0) {
-  // Colorize internal values.
-  let pretty = arr
-    .toString()
-    .split(',')
-    .map((elem) => yellow(elem));
+function stringifyTypedArray(arr, ctx, depth = 0) {
+  // Note: BigInt elements are suffixed with `n` (e.g. `BigInt64Array`).
+  const suffix = typeof arr[0] === 'bigint' ? 'n' : '';
+  const limit = Math.min(arr.length, ctx.iterableLimit);
+  let pretty = Array.from({ length: limit }, (_, i) =>
+    yellow(stringifyNumber(arr[i], ctx) + suffix)
+  );
 
   // Get typed-array's specific type.
-  const type = Object.prototype.toString
-    .call(arr)
-    .replace('[object ', '')
-    .replace(']', '');
+  const type = objectToString(arr).replace('[object ', '').replace(']', '');
+  const marker = arr.length > limit ? moreMembers(arr.length - limit) : '';
+
+  if (arr.length === 0) return `${type}(0) []`;
 
   if (pretty.length > 50) {
-    pretty = prettifyArray(pretty, depth, true);
+    pretty = prettifyArray(pretty, ctx, depth, true);
+    pretty = appendMarker(pretty, marker, depth);
     return `${type}(${arr.length}) [\n${pretty}\n${pre((depth - 1) * 2)}]`;
   }
 
+  if (marker) pretty.push(marker);
   return `${type}(${arr.length}) [ ${pretty.join(', ')} ]`;
 }
 
+function isDataView(value) {
+  return objectToString(value) === '[object DataView]';
+}
+
+function stringifyDataView(value, ctx, depth) {
+  const byteLength = intrinsic(DataView.prototype, 'byteLength', value);
+  const byteOffset = intrinsic(DataView.prototype, 'byteOffset', value);
+  const buffer = intrinsic(DataView.prototype, 'buffer', value);
+  const entries = [
+    `${pre(depth * 2)}byteLength: ${stringify(byteLength, ctx, depth)}`,
+    `${pre(depth * 2)}byteOffset: ${stringify(byteOffset, ctx, depth)}`,
+    `${pre(depth * 2)}buffer: ${stringifyArrayBuffer(buffer)}`,
+  ];
+  return stringifyEntries(entries, ctx, depth, 'DataView ');
+}
+
 function isDate(value) {
   return Object.prototype.toString.call(value) === '[object Date]';
 }
 
 function stringifyDate(date) {
-  return date.toISOString();
+  // Note: Calling `toISOString` on invalid dates throws a RangeError.
+  const time = Date.prototype.getTime.call(date);
+  return Number.isNaN(time)
+    ? 'Invalid Date'
+    : Date.prototype.toISOString.call(date);
+}
+
+function functionTag(fn) {
+  // Note: Classes can only be told apart from functions by their source.
+  if (Function.prototype.toString.call(fn).startsWith('class')) {
+    const parent = Object.getPrototypeOf(fn);
+    const heritage = parent?.name ? ` extends ${parent.name}` : '';
+    return `[class ${fn.name || '(anonymous)'}${heritage}]`;
+  }
+
+  // The type is one of Function, AsyncFunction, GeneratorFunction, etc.
+  const type = objectToString(fn).replace('[object ', '').replace(']', '');
+  return fn.name ? `[${type}: ${fn.name}]` : `[${type} (anonymous)]`;
+}
+
+function stringifyFunction(fn, ctx, depth) {
+  const tag = functionTag(fn);
+
+  // Show any own properties attached to the function.
+  const entries = objectEntries(fn, ctx, depth);
+  if (entries.length === 0) return cyan(tag);
+
+  return stringifyEntries(entries, ctx, depth, `${cyan(tag)} `);
 }
 
 function isRexExp(value) {
@@ -212,15 +436,79 @@ function isRexExp(value) {
 }
 
 function stringifyRexExp(exp) {
-  return exp.toString();
+  const { source, flags } = exp;
+  return red(`/${source}/${flags}`);
+}
+
+// The wrapper objects of the primitive types (e.g. `new Number(5)`).
+const boxedTypes = {
+  '[object Number]': Number,
+  '[object String]': String,
+  '[object Boolean]': Boolean,
+  '[object BigInt]': BigInt,
+  '[object Symbol]': Symbol,
+};
+
+function isBoxedPrimitive(value) {
+  const type = boxedTypes[Object.prototype.toString.call(value)];
+  if (!type) return false;
+  // Note: Only actual wrapper objects can be unwrapped (`Symbol.toStringTag`
+  // may be spoofed by regular objects).
+  try {
+    type.prototype.valueOf.call(value);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+function stringifyBoxedPrimitive(value, ctx) {
+  const type = boxedTypes[Object.prototype.toString.call(value)];
+  const primitive = type.prototype.valueOf.call(value);
+  return `[${type.name}: ${stringify(primitive, ctx, 1)}]`;
 }
 
 function isError(value) {
   return Object.prototype.toString.call(value) === '[object Error]';
 }
 
-function stringifyError(error) {
-  return error.stack;
+// Returns the stack of an error, falling back to its name and message (read
+// without invoking their getters).
+//
+// Note: V8 formats the stack lazily, reading the name (through its getter).
+function errorHeader(error) {
+  try {
+    const stack = error.stack;
+    if (stack) return String(stack);
+  } catch {
+    // Fall back to the name and message.
+  }
+
+  const name = safeProperty(error, 'name', '[Getter]');
+  const message = safeProperty(error, 'message', '[Getter]');
+  return `${name}: ${message}`;
+}
+
+function stringifyError(error, ctx, depth) {
+  const header = errorHeader(error);
+
+  // Nested errors (e.g. causes) should be aligned with their parent.
+  const stack = header.split('\n').join(`\n${pre((depth - 1) * 2)}`);
+
+  // Own enumerable properties are shown along with the `cause` chain.
+  const entries = Object.keys(error).map((key) => {
+    const keyValue = stringifyKey(key);
+    return `${pre(depth * 2)}${keyValue}: ${stringify(error[key], ctx, depth)}`;
+  });
+
+  if (Object.hasOwn(error, 'cause') && !Object.keys(error).includes('cause')) {
+    const cause = stringify(error.cause, ctx, depth);
+    entries.push(`${pre(depth * 2)}[cause]: ${cause}`);
+  }
+
+  if (entries.length === 0) return stack;
+
+  return `${stack} {\n${entries.join(',\n')}\n${pre((depth - 1) * 2)}}`;
 }
 
 function isArrayBuffer(value) {
@@ -228,51 +516,242 @@ function isArrayBuffer(value) {
 }
 
 function stringifyArrayBuffer(value) {
-  return `ArrayBuffer { byteLength: ${stringify(value.byteLength)} }`;
+  const byteLength = intrinsic(ArrayBuffer.prototype, 'byteLength', value);
+  return `ArrayBuffer { byteLength: ${stringify(byteLength)} }`;
 }
 
 function isPromise(value) {
   return value instanceof Promise;
 }
 
-function stringifyPromise(value) {
+function stringifyPromise(value, ctx, depth) {
   // We have to use a Rust binding to inspect the contents of a promise
   // object because JS doesn't expose that kind of functionality.
+  //
+  // Note: Peeking never subscribes to the promise (e.g. by calling `then`),
+  // so inspecting it has no side effects on the program.
   const binding = process.binding('promise');
   const { state, value: promiseValue } = binding.peek(value);
 
-  if (state === 'PENDING') {
-    return `Promise { ${cyan('<pending>')} }`;
+  const output =
+    state === 'PENDING'
+      ? cyan('<pending>')
+      : stringify(promiseValue, ctx, depth);
+
+  const label = state === 'REJECTED' ? `${red('<rejected>')} ` : '';
+  const entries = [`${pre(depth * 2)}${label}${output}`];
+
+  return stringifyEntries(entries, ctx, depth, 'Promise ');
+}
+
+function stringifyAccessor(object, descriptor, ctx, depth) {
+  // Label the accessor based on its type.
+  const { get, set } = descriptor;
+  const label = get && set ? 'Getter/Setter' : get ? 'Getter' : 'Setter';
+
+  if (!get || !ctx.getters) {
+    return cyan(`[${label}]`);
   }
 
-  const output = stringify(promiseValue, undefined, 1);
-  const end = `${output.length > 50 ? '\n' : ' '}}`;
+  // Evaluate the getter and display its value.
+  try {
+    const value = stringify(get.call(object), ctx, depth);
+    return `${cyan(`[${label}:`)} ${value}${cyan(']')}`;
+  } catch (err) {
+    return cyan(`[${label}: <Inspection threw (${err?.message})>]`);
+  }
+}
 
-  const prefix =
-    state === 'FULFILLED'
-      ? `${output.length > 50 ? '\n  ' : ''}`
-      : `${output.length > 50 ? '\n  ' : ''}${red('<rejected>')} `;
+const identifierRegex = /^[A-Za-z_$][\w$]*$/;
 
-  return 'Promise { ' + prefix + output + end;
+// The well-known symbols (e.g. `Symbol.iterator`), by symbol.
+const wellKnownSymbols = new Map(
+  Object.getOwnPropertyNames(Symbol)
+    .filter((name) => typeof Symbol[name] === 'symbol')
+    .map((name) => [Symbol[name], `Symbol.${name}`])
+);
+
+// Object keys are quoted unless they are valid identifiers (e.g. `'a-b'`,
+// `'123'` and `''` are quoted).
+//
+// Note: Symbol keys are shown in brackets, telling well-known and registered
+// symbols apart (e.g. `[Symbol.iterator]` and `[Symbol.for(id)]`).
+function stringifyKey(key) {
+  if (typeof key === 'symbol') {
+    const registered = Symbol.keyFor(key);
+    const name =
+      wellKnownSymbols.get(key) ??
+      (registered !== undefined ? `Symbol.for(${registered})` : String(key));
+    return `[${green(name)}]`;
+  }
+  return identifierRegex.test(key) ? key : quoteText(key);
+}
+
+function isMap(value) {
+  return value instanceof Map;
+}
+
+function isSet(value) {
+  return value instanceof Set;
+}
+
+function stringifyMapOrSet(value, ctx, depth) {
+  // Read the entries through a Rust binding so any user-defined
+  // `Symbol.iterator` overrides will not be triggered.
+  const binding = process.binding('util');
+  const flatEntries = binding.previewEntries(value);
+  const type = isMap(value) ? 'Map' : 'Set';
+  const size = type === 'Map' ? flatEntries.length / 2 : flatEntries.length;
+  const step = type === 'Map' ? 2 : 1;
+  const limit = Math.min(size, ctx.iterableLimit);
+
+  // Stringifies a single Map/Set member.
+  const member = (elem) => stringify(elem, ctx, depth);
+
+  // Group the Map entries as pairs (Set entries are standalone).
+  let members = [];
+  for (let i = 0; i < size * step; i += step) {
+    members.push(flatEntries.slice(i, i + step));
+  }
+
+  // Sorting requires the (stringified) Map keys or Set values of all entries.
+  if (ctx.sorted) {
+    const sortKey = ([elem]) => stripColors(member(elem));
+    members = members
+      .map((pair) => [sortKey(pair), pair])
+      .sort(([a], [b]) => (a < b ? -1 : a > b ? 1 : 0))
+      .map(([, pair]) => pair);
+  }
+
+  const entries = members
+    .slice(0, limit)
+    .map(([key, value]) =>
+      type === 'Map' ? `${member(key)} => ${member(value)}` : member(key)
+    );
+
+  // Show how many entries were left out.
+  if (size > limit) {
+    const noun = type === 'Map' ? ['entry', 'entries'] : undefined;
+    entries.push(moreMembers(size - limit, noun));
+  }
+
+  const prefix = `${type}(${size}) `;
+
+  if (entries.length === 0) return `${prefix}{}`;
+
+  // Apply multi-line formatting on long entries.
+  if (!fitsInline(entries, ctx, depth, prefix)) {
+    const start = `${prefix}{\n`;
+    const end = `\n${pre((depth - 1) * 2)}}`;
+    const lines = entries.map((entry) => `${pre(depth * 2)}${entry}`);
+    return `${start}${lines.join(',\n')}${end}`;
+  }
+
+  return `${prefix}{ ${entries.join(', ')} }`;
+}
+
+/**
+ * Keeps track of the objects being stringified so circular references
+ * can be labeled (e.g. `<ref *1> { self: [Circular *1] }`).
+ *
+ * @ignore
+ * @param {*} value
+ * @param {Object} ctx
+ * @param {number} depth
+ * @returns {string}
+ */
+
+function stringifyReference(value, ctx, depth) {
+  // Note: Checking the depth before recursing any further keeps deeply
+  // nested values (e.g. long linked lists) from overflowing the stack.
+  if (depth > ctx.depth + 1) {
+    return cyan(stringifyTruncated(value));
+  }
+
+  // A reference to an ancestor object means that we have a cycle.
+  if (ctx.seen.includes(value)) {
+    if (!ctx.circular.has(value)) {
+      ctx.circular.set(value, ctx.circular.size + 1);
+    }
+    return cyan(`[Circular *${ctx.circular.get(value)}]`);
+  }
+
+  // Note: Tracks the deepest (non-empty) object nested in this one.
+  const deepest = ctx.deepest;
+  ctx.deepest = 0;
+
+  ctx.seen.push(value);
+  const output = stringifyObject(value, ctx, depth);
+  ctx.seen.pop();
+
+  ctx.deepest = Math.max(deepest, ctx.deepest);
+
+  // Label the object if it's the target of a circular reference.
+  const ref = ctx.circular.get(value);
+  return ref ? `${cyan(`<ref *${ref}>`)} ${output}` : output;
 }
 
-const specialCharsRegex = new RegExp('[^A-Za-z0-9|_]+');
+// Describes a value nested deeper than the depth limit (e.g. `[Object]`).
+function stringifyTruncated(value) {
+  if (typeof value === 'function') return functionTag(value);
+  if (isArray(value)) return '[Array]';
+
+  const type = objectToString(value).replace('[object ', '').replace(']', '');
+  if (Object.getPrototypeOf(value) === null) return '[Object: null prototype]';
+
+  const name = type === 'Object' ? constructorName(value) : type;
+  return `[${name || 'Object'}]`;
+}
 
 /**
  * Specifically stringifies JavaScript objects.
  *
  * @ignore
  * @param {*} value
- * @param {WeakSet} seen
+ * @param {Object} ctx
  * @param {number} depth
  * @returns {string}
  */
 
-function stringifyObject(value, seen = new WeakSet(), depth) {
+// Stringifies a Proxy object, through its target (by default) or revealing
+// both its target and handler (with the `showProxy` option).
+//
+// Note: The details are read through a Rust binding so no trap is invoked.
+function stringifyProxy([target, handler], ctx, depth) {
+  if (target === null) return cyan('<Revoked Proxy>');
+  if (!ctx.showProxy) return stringifyObject(target, ctx, depth);
+
+  const entries = [
+    stringify(target, ctx, depth),
+    stringify(handler, ctx, depth),
+  ];
+
+  if (!fitsInline(entries, ctx, depth)) {
+    const indented = entries.map((entry) => `${pre(depth * 2)}${entry}`);
+    return `Proxy [\n${indented.join(',\n')}\n${pre((depth - 1) * 2)}]`;
+  }
+
+  return `Proxy [ ${entries.join(', ')} ]`;
+}
+
+function stringifyObject(value, ctx = createContext(), depth) {
+  const proxy = process.binding('util').getProxyDetails(value);
+  if (proxy) {
+    return stringifyProxy(proxy, ctx, depth);
+  }
+
   // We have to check the type of the value parameter to decide which stringify
   // transformer we should use.
+  if (typeof value === 'function') {
+    return stringifyFunction(value, ctx, depth);
+  }
+
   if (isArray(value)) {
-    return stringifyArray(value, seen, depth);
+    return stringifyArray(value, ctx, depth);
+  }
+
+  if (isArguments(value)) {
+    return `[Arguments] ${stringifyArray(value, ctx, depth)}`;
   }
 
   if (isArrayBuffer(value)) {
@@ -280,7 +759,11 @@ function stringifyObject(value, seen = new WeakSet(), depth) {
   }
 
   if (isTypedArray(value)) {
-    return stringifyTypedArray(value, depth);
+    return stringifyTypedArray(value, ctx, depth);
+  }
+
+  if (isDataView(value)) {
+    return stringifyDataView(value, ctx, depth);
   }
 
   if (isDate(value)) {
@@ -291,12 +774,24 @@ function stringifyObject(value, seen = new WeakSet(), depth) {
     return stringifyRexExp(value);
   }
 
+  if (isBoxedPrimitive(value)) {
+    return stringifyBoxedPrimitive(value, ctx);
+  }
+
   if (isError(value)) {
-    return stringifyError(value);
+    return stringifyError(value, ctx, depth);
   }
 
   if (isPromise(value)) {
-    return stringifyPromise(value);
+    return stringifyPromise(value, ctx, depth);
+  }
+
+  if (isMap(value) || isSet(value)) {
+    return stringifyMapOrSet(value, ctx, depth);
+  }
+
+  if (value === globalThis) {
+    return stringifyGlobal(value, ctx, depth);
   }
 
   // It's an object type that console does not support.
@@ -306,28 +801,75 @@ function stringifyObject(value, seen = new WeakSet(), depth) {
   }
 
   // Looks like it's a regular object.
+  const entries = objectEntries(value, ctx, depth);
+
+  // Output the class name if the object is a class instance.
+  //
+  // Note: The constructor is read without invoking prototype getters, which
+  // may throw for host objects.
+  const className = constructorName(value);
+  let prefix = !className || className === 'Object' ? '' : className + ' ';
+
+  // Objects without a prototype (e.g. `Object.create(null)`) are labeled.
+  if (Object.getPrototypeOf(value) === null) {
+    prefix = '[Object: null prototype] ';
+  }
+
+  return stringifyEntries(entries, ctx, depth, prefix);
+}
+
+// Stringifies the global object as a summary of its own properties.
+//
+// Note: It references itself and every built-in (e.g. `process`), so nested
+// objects are always truncated (as if the depth limit was reached).
+function stringifyGlobal(value, ctx, depth) {
+  const entries = objectEntries(value, { ...ctx, depth: depth - 1 }, depth);
+  return stringifyEntries(entries, ctx, depth, 'Object [global] ');
+}
+
+// Stringifies the own enumerable properties of an object (or function), and
+// its symbol-keyed ones (with the `showHidden` option).
+function objectEntries(value, ctx, depth) {
   const entries = [];
-  for (const key of Object.keys(value)) {
-    if (seen.has(value[key])) {
-      entries.push(`${pre(depth * 2)}${key}: [Circular]`);
-      continue;
+  const keys = ctx.sorted ? Object.keys(value).sort() : Object.keys(value);
+
+  if (ctx.showHidden) {
+    const symbols = Object.getOwnPropertySymbols(value);
+    const description = (symbol) => stripColors(stringifyKey(symbol));
+    if (ctx.sorted) {
+      symbols.sort((a, b) => (description(a) < description(b) ? -1 : 1));
     }
-    // The following wraps in quotes object keys that contain special
-    // characters like { "Foo-Bar": 123 }.
-    const keyValue = specialCharsRegex.test(key) ? `"${key}"` : key;
+    keys.push(...symbols);
+  }
 
-    seen.add(value);
-    entries.push(
-      `${pre(depth * 2)}${keyValue}: ${stringify(value[key], seen, depth)}`
-    );
+  for (const key of keys.slice(0, ctx.iterableLimit)) {
+    const keyValue = stringifyKey(key);
+
+    // Accessors are not invoked (unless requested) to avoid side effects.
+    const descriptor = Object.getOwnPropertyDescriptor(value, key);
+    const output =
+      descriptor.get || descriptor.set
+        ? stringifyAccessor(value, descriptor, ctx, depth)
+        : stringify(value[key], ctx, depth);
+
+    entries.push(`${pre(depth * 2)}${keyValue}: ${output}`);
   }
 
-  // Output the class name if the object is a class instance.
-  const className = value?.constructor?.name;
-  const prefix = !className || className === 'Object' ? '' : className + ' ';
+  // Show how many keys were left out.
+  if (keys.length > ctx.iterableLimit) {
+    const marker = moreMembers(keys.length - ctx.iterableLimit, [
+      'entry',
+      'entries',
+    ]);
+    entries.push(`${pre(depth * 2)}${marker}`);
+  }
 
+  return entries;
+}
+
+function stringifyEntries(entries, ctx, depth, prefix) {
   // Apply multi-line formatting on long properties.
-  if (entries.map((v) => v.trim()).join('').length > 50) {
+  if (!fitsInline(entries.map((v) => v.trim()), ctx, depth, prefix)) {
     const start = `${prefix}{\n`;
     const end = `\n${pre((depth - 1) * 2)}}`;
     return `${start}${entries.join(',\n')}${end}`;
@@ -340,6 +882,30 @@ function stringifyObject(value, seen = new WeakSet(), depth) {
   return `${prefix}${content}`;
 }
 
+/**
+ * Converts the given value into a string in the same way `console.log` does.
+ *
+ * @param {*} value - The value to be inspected.
+ * @param {Object} [options] - Inspection options.
+ * @param {Boolean} [options.colors] - Whether the output should be colored (default: true).
+ * @param {Number} [options.iterableLimit] - Maximum number of array items, Map/Set entries and object keys to show (default: 100).
+ * @param {Boolean} [options.numericSeparator] - Group digits of large numbers with underscores (default: false).
+ * @param {Boolean} [options.getters] - Evaluate getters and display their values (default: false).
+ * @param {Boolean} [options.sorted] - Sort object keys and Map/Set entries (default: false).
+ * @param {Number} [options.strAbbreviateSize] - Maximum number of characters of nested strings to show (default: 100).
+ * @param {Number} [options.depth] - How many levels deep nested objects are shown (default: 4).
+ * @param {Number|Boolean} [options.compact] - Combine the innermost N levels of objects on a single line if they fit, `true` to always combine them and `false` to never (default: 3).
+ * @param {Number} [options.breakLength] - The line length at which objects are split into multiple lines (default: 80).
+ * @param {Boolean} [options.showProxy] - Show proxies as `Proxy [ target, handler ]` instead of through their target (default: false).
+ * @param {Boolean} [options.showHidden] - Show the symbol-keyed properties of objects (default: false).
+ * @returns {String}
+ */
+export function inspect(value, options = {}) {
+  const ctx = createContext(options);
+  const output = stringify(value, ctx);
+  return ctx.colors ? output : stripColors(output);
+}
+
 /**
  * Shows the given message and waits for the user's input.
  *
@@ -355,6 +921,25 @@ export function prompt(message = 'Prompt', defaultValue = null) {
   return process.stdin.read() || defaultValue;
 }
 
+// The sink receives everything written to the console.
+let sink = (stream, output) => process[stream].write(output);
+
+/**
+ * Replaces the sink console output is written to.
+ *
+ * @param {Function} fn - A function receiving the stream name (`stdout` or `stderr`) and the output.
+ * @returns {Function} The previous sink.
+ */
+export function setConsoleSink(fn) {
+  // Check the fn argument type.
+  if (typeof fn !== 'function') {
+    throw new TypeError(`The "fn" argument must be of type function.`);
+  }
+  const previous = sink;
+  sink = fn;
+  return previous;
+}
+
 /**
  * Console is a subset implementation of MDN's Console API.
  */
@@ -369,8 +954,10 @@ export class Console {
    * @param  {...*} args - Prints to stdout with newline.
    */
   log(...args) {
-    const output = args.map((arg) => stringify(arg)).join(' ');
-    process.stdout.write(`${output}\n`);
+    const output = args
+      .map((arg) => stringify(arg, createConsoleContext()))
+      .join(' ');
+    sink('stdout', `${output}\n`);
   }
 
   /**
@@ -379,8 +966,10 @@ export class Console {
    * @param  {...*} args - Prints to stdout with newline.
    */
   info(...args) {
-    const output = args.map((arg) => stringify(arg)).join(' ');
-    process.stdout.write(`${output}\n`);
+    const output = args
+      .map((arg) => stringify(arg, createConsoleContext()))
+      .join(' ');
+    sink('stdout', `${output}\n`);
   }
 
   /**
@@ -389,8 +978,10 @@ export class Console {
    * @param  {...*} args - Prints to stdout with newline.
    */
   debug(...args) {
-    const output = args.map((arg) => stringify(arg)).join(' ');
-    process.stdout.write(`${output}\n`);
+    const output = args
+      .map((arg) => stringify(arg, createConsoleContext()))
+      .join(' ');
+    sink('stdout', `${output}\n`);
   }
 
   /**
@@ -399,8 +990,10 @@ export class Console {
    * @param  {...*} args - Prints to stdout with newline.
    */
   warn(...args) {
-    const output = args.map((arg) => stringify(arg)).join(' ');
-    process.stderr.write(`WARNING: ${output}\n`);
+    const output = args
+      .map((arg) => stringify(arg, createConsoleContext()))
+      .join(' ');
+    sink('stderr', `WARNING: ${output}\n`);
   }
 
   /**
@@ -409,8 +1002,10 @@ export class Console {
    * @param  {...*} args - Prints to stdout with newline.
    */
   error(...args) {
-    const output = args.map((arg) => stringify(arg)).join(' ');
-    process.stderr.write(`WARNING: ${output}\n`);
+    const output = args
+      .map((arg) => stringify(arg, createConsoleContext()))
+      .join(' ');
+    sink('stderr', `WARNING: ${output}\n`);
   }
 
   /**
@@ -498,4 +1093,4 @@ export function wrapConsole(console, consoleFromV8) {
   }
 }
 
-export default { Console, prompt, wrapConsole };
+export default { Console, inspect, prompt, setConsoleSink, wrapConsole };
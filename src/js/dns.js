@@ -10,7 +10,9 @@
  * @module DNS
  */
 
-const binding = process.binding('dns');
+import { trackBindingOps } from 'util';
+
+const binding = trackBindingOps(process.binding('dns'), 'dns');
 
 /**
  * @constant {RegExp} - A regular expression that matches IPv4 addresses.
@@ -57,6 +59,35 @@ export async function lookup(hostname) {
   return binding.lookup(hostname);
 }
 
+/**
+ * @constant {string[]} - The supported DNS record types.
+ */
+export const RECORD_TYPES = ['A', 'AAAA', 'CNAME', 'MX', 'TXT'];
+
+/**
+ * Uses the DNS protocol to resolve the records of a specific type for a host name.
+ *
+ * @param {String} hostname - Host name to resolve.
+ * @param {String} [recordType] - The record type (`A`, `AAAA`, `CNAME`, `MX` or `TXT`).
+ * @returns {Promise<Array>} An array of the resolved records.
+ */
+export async function resolve(hostname, recordType = 'A') {
+  // Check the data argument type.
+  if (!hostname || typeof hostname !== 'string') {
+    throw new TypeError(`The "hostname" argument must be of type string.`);
+  }
+
+  // Check if the record type is supported.
+  if (!RECORD_TYPES.includes(recordType)) {
+    throw new TypeError(
+      `The "recordType" argument must be one of: ${RECORD_TYPES.join(', ')}.`
+    );
+  }
+
+  return binding.resolve(hostname, recordType);
+}
+
 export default {
   lookup,
+  resolve,
 };
@@ -0,0 +1,145 @@
+// Event Target API
+//
+// The EventTarget interface is implemented by objects that can receive events
+// and may have listeners for them (e.g. `AbortSignal` and `globalThis`).
+//
+// https://developer.mozilla.org/en-US/docs/Web/API/EventTarget
+
+/**
+ * The `Event` interface represents an event which takes place on an `EventTarget`.
+ */
+export class Event {
+  #stopped = false;
+
+  /**
+   * Creates a new event instance.
+   *
+   * @param {String} type - The name of the event.
+   * @param {Object} [options] - Event options (e.g. `cancelable`).
+   * @returns {Event}
+   */
+  constructor(type, options = {}) {
+    if (arguments.length === 0) {
+      throw new TypeError('The "type" argument must be specified.');
+    }
+    this.type = String(type);
+    this.cancelable = Boolean(options?.cancelable);
+    this.defaultPrevented = false;
+    this.target = null;
+    this.currentTarget = null;
+    this.timeStamp = Date.now();
+  }
+
+  /**
+   * Cancels the event (if it is cancelable).
+   */
+  preventDefault() {
+    if (this.cancelable) this.defaultPrevented = true;
+  }
+
+  /**
+   * Prevents the remaining listeners from being called.
+   */
+  stopImmediatePropagation() {
+    this.#stopped = true;
+  }
+
+  /**
+   * Whether `stopImmediatePropagation` has been called.
+   * @ignore
+   */
+  get immediatePropagationStopped() {
+    return this.#stopped;
+  }
+}
+
+// Holds the registered event listeners of every target, grouped by event type.
+//
+// Note: Keeping them outside of the instances allows the methods to be used
+// by objects that were not created by the constructor (e.g. `globalThis`).
+const targets = new WeakMap();
+
+function listenersOf(target, type) {
+  if (!targets.has(target)) targets.set(target, new Map());
+  const listeners = targets.get(target);
+  if (!listeners.has(type)) listeners.set(type, []);
+  return listeners.get(type);
+}
+
+/**
+ * The `EventTarget` interface is implemented by objects that can receive
+ * events and may have listeners for them.
+ */
+export class EventTarget {
+  /**
+   * Registers an event listener.
+   *
+   * @param {String} type - The event type to listen for.
+   * @param {Function|Object} handler - A function, or an object with a `handleEvent` method.
+   * @param {Object} [options] - Listener options (e.g. `once`).
+   */
+  addEventListener(type, handler, options = {}) {
+    if (!handler) return;
+
+    // Registering the same handler twice has no effect.
+    const listeners = listenersOf(this, type);
+    if (listeners.some((listener) => listener.handler === handler)) return;
+
+    listeners.push({ handler, once: Boolean(options?.once) });
+  }
+
+  /**
+   * Removes a previously registered event listener.
+   *
+   * @param {String} type - The event type.
+   * @param {Function|Object} handler - The handler to remove.
+   */
+  removeEventListener(type, handler) {
+    const listeners = listenersOf(this, type);
+    const index = listeners.findIndex((entry) => entry.handler === handler);
+    if (index !== -1) listeners.splice(index, 1);
+  }
+
+  /**
+   * Dispatches an event to the `on<type>` handler and the registered listeners.
+   *
+   * @param {Event|String} event - The event (or the event type) to dispatch.
+   * @returns {Boolean} `false` if the event was cancelled, `true` otherwise.
+   */
+  dispatchEvent(event) {
+    if (typeof event === 'string') event = new Event(event);
+
+    event.target = this;
+    event.currentTarget = this;
+
+    // Note: Iterating over a copy ensures that listeners added or removed
+    // by other listeners don't affect the current dispatch.
+    const listeners = [...listenersOf(this, event.type)];
+    const onHandler = this[`on${event.type}`];
+
+    if (typeof onHandler === 'function') {
+      listeners.unshift({ handler: onHandler, once: false });
+    }
+
+    for (const { handler, once } of listeners) {
+      if (event.immediatePropagationStopped) break;
+      if (once) this.removeEventListener(event.type, handler);
+
+      // A throwing listener never prevents the rest from being called.
+      try {
+        if (typeof handler === 'function') {
+          handler.call(this, event);
+        } else {
+          handler.handleEvent?.(event);
+        }
+      } catch (err) {
+        globalThis.reportError(err);
+      }
+    }
+
+    event.currentTarget = null;
+    return !event.defaultPrevented;
+  }
+}
+
+export default { Event, EventTarget };
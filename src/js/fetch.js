@@ -7,6 +7,15 @@
 
 import http from 'http';
 
+// Keeps the idle connections of previous requests, so they can be reused.
+//
+// Note: Idle connections keep the event-loop alive (until they time out), so
+// requests only use the pool when they opt in with `keepAlive`.
+const pool = new http.ConnectionPool();
+
+// Content encodings that are decompressed automatically.
+const ENCODINGS = ['gzip', 'x-gzip', 'deflate', 'br'];
+
 // Utility function that combines uint8arrays.
 function concatUint8Arrays(...arrays) {
   return arrays.reduce(
@@ -15,6 +24,26 @@ function concatUint8Arrays(...arrays) {
   );
 }
 
+// Decompresses the response body while it's read (if it's encoded).
+function decompress({ statusCode, headers, body }) {
+  const encoding = headers['content-encoding']?.trim().toLowerCase();
+  if (!ENCODINGS.includes(encoding)) {
+    return { statusCode, headers, body };
+  }
+
+  // Note: The content-length refers to the encoded body, so it's no
+  // longer accurate once decompressed.
+  const decodedHeaders = { ...headers };
+  delete decodedHeaders['content-encoding'];
+  delete decodedHeaders['content-length'];
+
+  return {
+    statusCode,
+    headers: decodedHeaders,
+    body: body.decompress(encoding),
+  };
+}
+
 /**
  * The Response interface of the Fetch API represents the response to a request.
  * https://developer.mozilla.org/en-US/docs/Web/API/Response
@@ -124,12 +153,29 @@ class Response {
  *
  * @param {String} url
  * @param {Object} options
+ * @param {Boolean} [options.raw] - Skip the decompression of the response body.
+ * @param {Boolean} [options.keepAlive] - Reuse (keep-alive) connections to the same host (default: `false`).
  *
  * @returns Promise<Response>
  */
 async function fetch(url, options = {}) {
+  const { raw = false, keepAlive = false, ...requestOptions } = options;
+  const headers = { ...requestOptions.headers };
+  const hasAcceptEncoding = Object.keys(headers).some(
+    (name) => name.toLowerCase() === 'accept-encoding'
+  );
+
+  if (!raw && !hasAcceptEncoding) {
+    headers['accept-encoding'] = 'gzip, deflate, br';
+  }
+
   // Fetch is a wrapper around `http.request`.
-  return new Response(await http.request(url, options));
+  const response = await http.request(url, {
+    ...requestOptions,
+    headers,
+    pool: keepAlive ? pool : undefined,
+  });
+  return new Response(raw ? response : decompress(response));
 }
 
 export default fetch;
@@ -9,7 +9,9 @@
  * @module File-System
  */
 
-const binding = process.binding('fs');
+import { trackBindingOps, trackResource, untrackResource } from 'util';
+
+const binding = trackBindingOps(process.binding('fs'), 'fs');
 
 const BUFFER_SIZE = 40 * 1024; // 40KB bytes buffer when reading.
 
@@ -21,7 +23,7 @@ export class File {
    * Creates a new File instance given a file path.
    *
    * @param {String} path - The file path for the File instance.
-   * @param {String} [mode] - The mode in which the file is to be opened.
+   * @param {(String|Object)} [mode] - The mode (or open options) in which the file is to be opened.
    * @returns {File} An instance of the File class.
    */
   constructor(path, mode) {
@@ -49,6 +51,7 @@ export class File {
 
     this._handle = await binding.open(this.path, this.mode || mode);
     this.fd = this._handle.fd;
+    trackResource(this, `A file (rid ${this.fd}) at "${this.path}"`);
   }
 
   /**
@@ -64,6 +67,7 @@ export class File {
 
     this._handle = binding.openSync(this.path, this.mode || mode);
     this.fd = this._handle.fd;
+    trackResource(this, `A file (rid ${this.fd}) at "${this.path}"`);
   }
 
   /**
@@ -201,6 +205,75 @@ export class File {
     return binding.statSync(this.path);
   }
 
+  /**
+   * Acquires asynchronously an advisory lock on the file, waiting until
+   * any conflicting lock is released.
+   *
+   * @param {boolean} [exclusive] - Acquire an exclusive lock instead of a shared one.
+   */
+  async lock(exclusive = false) {
+    // Check if the file is open.
+    if (!this._handle) {
+      throw new Error('The file is not open.');
+    }
+
+    return binding.lock(this._handle, exclusive);
+  }
+
+  /**
+   * Acquires synchronously an advisory lock on the file, waiting until
+   * any conflicting lock is released.
+   *
+   * @param {boolean} [exclusive] - Acquire an exclusive lock instead of a shared one.
+   */
+  lockSync(exclusive = false) {
+    // Check if the file is open.
+    if (!this._handle) {
+      throw new Error('The file is not open.');
+    }
+
+    binding.lockSync(this._handle, exclusive);
+  }
+
+  /**
+   * Tries to acquire an advisory lock on the file without waiting.
+   *
+   * @param {boolean} [exclusive] - Acquire an exclusive lock instead of a shared one.
+   * @returns {boolean} Whether the lock was acquired.
+   */
+  tryLockSync(exclusive = false) {
+    // Check if the file is open.
+    if (!this._handle) {
+      throw new Error('The file is not open.');
+    }
+
+    return binding.tryLockSync(this._handle, exclusive);
+  }
+
+  /**
+   * Releases asynchronously the advisory lock held on the file.
+   */
+  async unlock() {
+    // Check if the file is open.
+    if (!this._handle) {
+      throw new Error('The file is not open.');
+    }
+
+    return binding.unlock(this._handle);
+  }
+
+  /**
+   * Releases synchronously the advisory lock held on the file.
+   */
+  unlockSync() {
+    // Check if the file is open.
+    if (!this._handle) {
+      throw new Error('The file is not open.');
+    }
+
+    binding.unlockSync(this._handle);
+  }
+
   /**
    * Closes the file asynchronously.
    */
@@ -211,6 +284,7 @@ export class File {
     }
 
     await binding.close(this._handle);
+    untrackResource(this);
 
     // Reset file object's attributes.
     this._handle = null;
@@ -227,6 +301,7 @@ export class File {
     }
 
     binding.closeSync(this._handle);
+    untrackResource(this);
 
     // Reset file object's attributes.
     this._handle = null;
@@ -489,37 +564,65 @@ function toUint8Array(data, encoding) {
   return data;
 }
 
+// Converts the write options into the options used to open the file.
+function toOpenOptions(options) {
+  const { append = false, create = true, createNew = false, mode } = options;
+  return {
+    write: true,
+    append: Boolean(append),
+    truncate: !append,
+    create: Boolean(create || createNew),
+    createNew: Boolean(createNew),
+    mode,
+  };
+}
+
 /**
  * Writes asynchronously contents to a file.
  *
  * @param {String} path - The path of the file where the data is to be written.
- * @param {(String|Uint8Array)} data - The data to write to the file.
+ * @param {(String|Uint8Array|AsyncIterable)} data - The data (or a stream of chunks) to write to the file.
  * @param {(String|Object)} [options] - The options to control the file write operation.
  * @param {String} [options.encoding] - The encoding to be used for writing the file.
+ * @param {Boolean} [options.append] - Appends the data to the file instead of overwriting it (default: false).
+ * @param {Boolean} [options.create] - Creates the file if it doesn't exist (default: true).
+ * @param {Boolean} [options.createNew] - Fails if the file already exists (default: false).
+ * @param {Number} [options.mode] - The permissions of the file when it is created (e.g. `0o644`, masked by the umask), ignored on Windows.
+ * @param {AbortSignal} [options.signal] - Allows cancelling the write operation.
  * @returns {Promise}
  */
 export async function writeFile(path, data, options = {}) {
   // Check the data argument type.
-  if (!(data instanceof Uint8Array) && typeof data !== 'string') {
+  const isStream = typeof data?.[Symbol.asyncIterator] === 'function';
+
+  if (!(data instanceof Uint8Array) && typeof data !== 'string' && !isStream) {
     throw new TypeError(
-      `The "data" argument must be of type string or Uint8Array.`
+      `The "data" argument must be of type string, Uint8Array or a stream.`
     );
   }
 
-  let encoding = typeof options === 'string' ? options : options.encoding;
+  if (typeof options === 'string') options = { encoding: options };
 
   // Default to utf-8 encoding.
-  if (!encoding) encoding = 'utf-8';
+  const encoding = options.encoding || 'utf-8';
+  const signal = options.signal;
 
-  const data_u8 = toUint8Array(data, encoding);
+  signal?.throwIfAborted();
 
   // Create a file instance.
-  const file = new File(path, 'w');
+  const file = new File(path, toOpenOptions(options));
 
   // Open file, write data, and close it.
   await file.open();
-  await file.write(data_u8);
-  await file.close();
+  try {
+    const chunks = isStream ? data : [data];
+    for await (const chunk of chunks) {
+      signal?.throwIfAborted();
+      await file.write(toUint8Array(chunk, encoding));
+    }
+  } finally {
+    await file.close();
+  }
 }
 
 /**
@@ -529,6 +632,10 @@ export async function writeFile(path, data, options = {}) {
  * @param {String|Uint8Array} data - The data to write to the file.
  * @param {String|Object} [options] - The options to control the file write operation.
  * @param {String} [options.encoding] - The encoding to be used for writing the file.
+ * @param {Boolean} [options.append] - Appends the data to the file instead of overwriting it (default: false).
+ * @param {Boolean} [options.create] - Creates the file if it doesn't exist (default: true).
+ * @param {Boolean} [options.createNew] - Fails if the file already exists (default: false).
+ * @param {Number} [options.mode] - The permissions of the file when it is created (e.g. `0o644`, masked by the umask), ignored on Windows.
  */
 
 export function writeFileSync(path, data, options = {}) {
@@ -539,20 +646,22 @@ export function writeFileSync(path, data, options = {}) {
     );
   }
 
-  let encoding = typeof options === 'string' ? options : options.encoding;
+  if (typeof options === 'string') options = { encoding: options };
 
   // Default to utf-8 encoding.
-  if (!encoding) encoding = 'utf-8';
-
+  const encoding = options.encoding || 'utf-8';
   const data_u8 = toUint8Array(data, encoding);
 
   // Create a file instance.
-  const file = new File(path, 'w');
+  const file = new File(path, toOpenOptions(options));
 
   // Open file, write data, and close it.
   file.openSync();
-  file.writeSync(data_u8);
-  file.closeSync();
+  try {
+    file.writeSync(data_u8);
+  } finally {
+    file.closeSync();
+  }
 }
 
 /**
@@ -632,6 +741,38 @@ export function statSync(path) {
   return stats;
 }
 
+/**
+ * Retrieves asynchronously statistics for the file, without following
+ * symbolic links (so a link's own statistics are returned).
+ *
+ * @param {String} path - The path of the file for which statistics are to be retrieved.
+ * @returns {Promise<FileStats>} An object containing the statistics of the file.
+ */
+export async function lstat(path) {
+  // Check the path argument type.
+  if (typeof path !== 'string') {
+    throw new TypeError('The "path" argument must be of type string.');
+  }
+
+  return binding.stat(path, false);
+}
+
+/**
+ * Retrieves synchronously statistics for the file, without following
+ * symbolic links (so a link's own statistics are returned).
+ *
+ * @param {String} path - The path of the file for which statistics are to be retrieved.
+ * @returns {Object} An object containing the statistics of the file.
+ */
+export function lstatSync(path) {
+  // Check the path argument type.
+  if (typeof path !== 'string') {
+    throw new TypeError('The "path" argument must be of type string.');
+  }
+
+  return binding.statSync(path, false);
+}
+
 /**
  * Creates directories asynchronously.
  *
@@ -900,6 +1041,116 @@ export function renameSync(from, to) {
   binding.renameSync(from, to);
 }
 
+/**
+ * Checks that an owner id is either a non-negative integer or null.
+ *
+ * @param {Number|null} id - The user or group id.
+ * @param {String} name - The argument name used in error messages.
+ */
+function checkOwnerId(id, name) {
+  if (id === null) return;
+  if (!Number.isInteger(id) || id < 0) {
+    throw new TypeError(
+      `The "${name}" argument must be a non-negative integer or null.`
+    );
+  }
+}
+
+/**
+ * Changes the owner and group of a file asynchronously (Unix only).
+ *
+ * @param {String} path - The path of the file.
+ * @param {Number|null} uid - The new owner's user id (`null` leaves it unchanged).
+ * @param {Number|null} gid - The new group id (`null` leaves it unchanged).
+ * @returns {Promise<void>}
+ */
+export async function chown(path, uid, gid) {
+  // Check the path argument type.
+  if (typeof path !== 'string') {
+    throw new TypeError('The "path" argument must be of type string.');
+  }
+
+  checkOwnerId(uid, 'uid');
+  checkOwnerId(gid, 'gid');
+
+  return binding.chown(path, uid, gid);
+}
+
+/**
+ * Changes the owner and group of a file synchronously (Unix only).
+ *
+ * @param {String} path - The path of the file.
+ * @param {Number|null} uid - The new owner's user id (`null` leaves it unchanged).
+ * @param {Number|null} gid - The new group id (`null` leaves it unchanged).
+ */
+export function chownSync(path, uid, gid) {
+  // Check the path argument type.
+  if (typeof path !== 'string') {
+    throw new TypeError('The "path" argument must be of type string.');
+  }
+
+  checkOwnerId(uid, 'uid');
+  checkOwnerId(gid, 'gid');
+
+  binding.chownSync(path, uid, gid);
+}
+
+/**
+ * Converts a `Date` or an epoch-seconds number to seconds.
+ *
+ * @param {Date|Number} time - The timestamp to be converted.
+ * @param {String} name - The argument name used in error messages.
+ * @returns {Number} The seconds since the Unix epoch.
+ */
+function toEpochSeconds(time, name) {
+  const seconds = time instanceof Date ? time.getTime() / 1000 : time;
+  if (!Number.isFinite(seconds) || seconds < 0) {
+    throw new TypeError(
+      `The "${name}" argument must be a valid Date or a non-negative number.`
+    );
+  }
+  return seconds;
+}
+
+/**
+ * Changes the access and modification times of a file asynchronously.
+ *
+ * @param {String} path - The path of the file.
+ * @param {Date|Number} atime - The access time (a Date or seconds since epoch).
+ * @param {Date|Number} mtime - The modification time (a Date or seconds since epoch).
+ * @returns {Promise<void>}
+ */
+export async function utime(path, atime, mtime) {
+  // Check the path argument type.
+  if (typeof path !== 'string') {
+    throw new TypeError('The "path" argument must be of type string.');
+  }
+
+  const atimeSecs = toEpochSeconds(atime, 'atime');
+  const mtimeSecs = toEpochSeconds(mtime, 'mtime');
+
+  return binding.utime(path, atimeSecs, mtimeSecs);
+}
+
+/**
+ * Changes the access and modification times of a file synchronously.
+ *
+ * @param {String} path - The path of the file.
+ * @param {Date|Number} atime - The access time (a Date or seconds since epoch).
+ * @param {Date|Number} mtime - The modification time (a Date or seconds since epoch).
+ */
+export function utimeSync(path, atime, mtime) {
+  // Check the path argument type.
+  if (typeof path !== 'string') {
+    throw new TypeError('The "path" argument must be of type string.');
+  }
+
+  const atimeSecs = toEpochSeconds(atime, 'atime');
+  const mtimeSecs = toEpochSeconds(mtime, 'mtime');
+
+  binding.utimeSync(path, atimeSecs, mtimeSecs);
+}
+
 /**
  * Returns an async iterator that watches for changes over a path.
  *
@@ -943,6 +1194,187 @@ export function createReadStream(path, options = {}) {
   };
 }
 
+// Converts a glob pattern (e.g. `src/**/*.js`) into a regular expression.
+function globToRegExp(glob) {
+  let source = '';
+  for (let i = 0; i < glob.length; i++) {
+    const char = glob[i];
+    if (char === '*' && glob[i + 1] === '*') {
+      // A globstar matches any number of (nested) directories.
+      const slash = glob[i + 2] === '/';
+      source += slash ? '(?:.*/)?' : '.*';
+      i += slash ? 2 : 1;
+    } else if (char === '*') {
+      source += '[^/]*';
+    } else if (char === '?') {
+      source += '[^/]';
+    } else {
+      source += char.replace(/[.+^${}()|[\]\\]/g, '\\$&');
+    }
+  }
+  return new RegExp(`^${source}$`);
+}
+
+// Normalizes the walk options, converting glob patterns to regexes.
+function walkOptions(options) {
+  const toRegExp = (pattern) =>
+    pattern instanceof RegExp ? pattern : globToRegExp(String(pattern));
+
+  return {
+    maxDepth: Infinity,
+    includeFiles: true,
+    includeDirs: true,
+    followSymlinks: false,
+    ...options,
+    exts: options.exts?.map((ext) => (ext.startsWith('.') ? ext : `.${ext}`)),
+    match: options.match?.map(toRegExp),
+    skip: options.skip?.map(toRegExp),
+  };
+}
+
+// Checks if a walked entry should be yielded, based on the walk options.
+function shouldInclude(entry, options) {
+  const { path, isDirectory } = entry;
+  const { exts, match, skip } = options;
+
+  if (isDirectory ? !options.includeDirs : !options.includeFiles) return false;
+  if (!isDirectory && exts && !exts.some((ext) => path.endsWith(ext))) {
+    return false;
+  }
+  if (match && !match.some((pattern) => pattern.test(path))) return false;
+  return !skip?.some((pattern) => pattern.test(path));
+}
+
+// Checks if a directory was already walked (symbolic links may form loops),
+// identifying it by its device and inode.
+function wasVisited(visited, { dev, inode }) {
+  if (inode === undefined) return false;
+  const id = `${dev}:${inode}`;
+  if (visited.has(id)) return true;
+  visited.add(id);
+  return false;
+}
+
+// Creates a walk entry given an entry's path and its (l)stat information.
+function walkEntry(path, name, stats, isSymlink) {
+  const { isFile, isDirectory } = stats;
+  return { path, name, isFile, isDirectory, isSymlink };
+}
+
+/**
+ * Recursively walks a directory tree, asynchronously yielding its entries
+ * (starting with the root directory itself).
+ *
+ * @param {String} root - The path of the directory to walk.
+ * @param {Object} [options] - Configuration options for the walk.
+ * @param {Number} [options.maxDepth] - How many levels deep to walk (default: Infinity).
+ * @param {Boolean} [options.includeFiles] - Whether to yield files (default: true).
+ * @param {Boolean} [options.includeDirs] - Whether to yield directories (default: true).
+ * @param {Boolean} [options.followSymlinks] - Whether to follow symbolic links (default: false).
+ * @param {String[]} [options.exts] - Only yield files with one of these extensions.
+ * @param {(RegExp|String)[]} [options.match] - Only yield paths matching one of these regexes (or globs).
+ * @param {(RegExp|String)[]} [options.skip] - Skip paths (and directories' contents) matching one of these regexes (or globs).
+ * @returns {AsyncIterable<Object>} The entries (`path`, `name`, `isFile`, `isDirectory` and `isSymlink`).
+ */
+export async function* walk(root, options = {}) {
+  // Check the root argument type.
+  if (typeof root !== 'string') {
+    throw new TypeError('The "root" argument must be of type string.');
+  }
+
+  options = walkOptions(options);
+
+  const name = root.split('/').filter(Boolean).at(-1) ?? root;
+  const rootStats = await stat(root);
+  const rootEntry = walkEntry(root, name, rootStats, false);
+  const visited = new Set();
+
+  if (shouldInclude(rootEntry, options)) yield rootEntry;
+  if (!rootEntry.isDirectory) return;
+
+  wasVisited(visited, rootStats);
+
+  async function* walkDir(path, depth) {
+    if (depth > options.maxDepth) return;
+
+    for (const name of (await readdir(path)).sort()) {
+      const entryPath = joinPaths(path, name);
+
+      // Symbolic links are only followed when requested.
+      let stats = await lstat(entryPath);
+      const isSymlink = stats.isSymbolicLink;
+      if (isSymlink && options.followSymlinks) stats = await stat(entryPath);
+
+      // Skipped directories are never walked into.
+      const entry = walkEntry(entryPath, name, stats, isSymlink);
+      if (options.skip?.some((pattern) => pattern.test(entryPath))) continue;
+
+      if (shouldInclude(entry, options)) yield entry;
+      if (entry.isDirectory && !wasVisited(visited, stats)) {
+        yield* walkDir(entryPath, depth + 1);
+      }
+    }
+  }
+
+  yield* walkDir(root, 1);
+}
+
+/**
+ * Recursively walks a directory tree, synchronously yielding its entries
+ * (starting with the root directory itself).
+ *
+ * @param {String} root - The path of the directory to walk.
+ * @param {Object} [options] - Configuration options for the walk (same as `walk`).
+ * @returns {Iterable<Object>} The entries (`path`, `name`, `isFile`, `isDirectory` and `isSymlink`).
+ */
+export function* walkSync(root, options = {}) {
+  // Check the root argument type.
+  if (typeof root !== 'string') {
+    throw new TypeError('The "root" argument must be of type string.');
+  }
+
+  options = walkOptions(options);
+
+  const name = root.split('/').filter(Boolean).at(-1) ?? root;
+  const rootStats = statSync(root);
+  const rootEntry = walkEntry(root, name, rootStats, false);
+  const visited = new Set();
+
+  if (shouldInclude(rootEntry, options)) yield rootEntry;
+  if (!rootEntry.isDirectory) return;
+
+  wasVisited(visited, rootStats);
+
+  function* walkDir(path, depth) {
+    if (depth > options.maxDepth) return;
+
+    for (const name of readdirSync(path).sort()) {
+      const entryPath = joinPaths(path, name);
+
+      // Symbolic links are only followed when requested.
+      let stats = lstatSync(entryPath);
+      const isSymlink = stats.isSymbolicLink;
+      if (isSymlink && options.followSymlinks) stats = statSync(entryPath);
+
+      // Skipped directories are never walked into.
+      const entry = walkEntry(entryPath, name, stats, isSymlink);
+      if (options.skip?.some((pattern) => pattern.test(entryPath))) continue;
+
+      if (shouldInclude(entry, options)) yield entry;
+      if (entry.isDirectory && !wasVisited(visited, stats)) {
+        yield* walkDir(entryPath, depth + 1);
+      }
+    }
+  }
+
+  yield* walkDir(root, 1);
+}
+
+// Joins two path segments (avoiding duplicate separators).
+function joinPaths(base, name) {
+  return base.endsWith('/') ? `${base}${name}` : `${base}/${name}`;
+}
+
 /**
  * Returns a new writable IO stream.
  *
@@ -982,6 +1414,8 @@ export default {
   copyFileSync,
   stat,
   statSync,
+  lstat,
+  lstatSync,
   mkdir,
   mkdirSync,
   rmdir,
@@ -992,7 +1426,13 @@ export default {
   rmSync,
   rename,
   renameSync,
+  chown,
+  chownSync,
+  utime,
+  utimeSync,
   watch,
+  walk,
+  walkSync,
   createReadStream,
   createWriteStream,
 };
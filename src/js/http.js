@@ -11,7 +11,7 @@
  */
 
 import net from 'net';
-import assert from 'assert';
+import { Assert as assert } from 'assert';
 import { EventEmitter } from 'events';
 
 const binding = process.binding('http_parser');
@@ -201,6 +201,103 @@ async function* wrapIterable(iterable) {
   }
 }
 
+/**
+ * Keeps the idle (keep-alive) connections of outgoing requests, so sequential
+ * requests to the same host reuse them instead of connecting again.
+ */
+export class ConnectionPool {
+  #idle;
+  #maxSocketsPerHost;
+  #keepAliveTimeout;
+
+  /**
+   * Creates a new connection pool.
+   *
+   * @param {Object} [options] - Configuration options for the pool.
+   * @param {Number} [options.maxSocketsPerHost] - How many idle connections are kept for every host (default: 6).
+   * @param {Number} [options.keepAliveTimeout] - How long (in ms) an idle connection is kept, before closing it (default: 1000).
+   * @returns {ConnectionPool}
+   */
+  constructor({ maxSocketsPerHost = 6, keepAliveTimeout = 1000 } = {}) {
+    this.#idle = new Map();
+    this.#maxSocketsPerHost = maxSocketsPerHost;
+    this.#keepAliveTimeout = keepAliveTimeout;
+  }
+
+  /**
+   * Takes an idle connection to the given host out of the pool.
+   *
+   * @param {String} hostname - The hostname of the remote host.
+   * @param {Number} port - The port of the remote host.
+   * @returns {(Socket|undefined)} A connected socket, if one is available.
+   */
+  acquire(hostname, port) {
+    const entries = this.#idle.get(`${hostname}:${port}`) ?? [];
+
+    let entry;
+    while ((entry = entries.pop())) {
+      entry.drop();
+      // Note: Sockets closed by the remote host are reset (and have no port).
+      if (entry.socket.remotePort !== undefined) return entry.socket;
+    }
+
+    return undefined;
+  }
+
+  /**
+   * Returns a connection to the pool (or closes it if the pool is full).
+   *
+   * @param {String} hostname - The hostname of the remote host.
+   * @param {Number} port - The port of the remote host.
+   * @param {Socket} socket - The (idle) connected socket.
+   */
+  release(hostname, port, socket) {
+    const key = `${hostname}:${port}`;
+    const entries = this.#idle.get(key) ?? [];
+
+    if (entries.length >= this.#maxSocketsPerHost) {
+      socket.end();
+      return;
+    }
+
+    // Removes the connection from the pool, e.g. when the remote host drops
+    // it while it's idle (nobody else is listening for its errors by then).
+    const entry = { socket };
+    entry.drop = () => {
+      const index = entries.indexOf(entry);
+      if (index !== -1) entries.splice(index, 1);
+      clearTimeout(entry.timer);
+      socket.off('error', entry.evict);
+      socket.off('end', entry.drop);
+    };
+
+    // Note: A socket closes itself on `end`, but not on errors.
+    entry.evict = () => {
+      entry.drop();
+      socket.destroy();
+    };
+
+    // Note: Idle connections keep the event-loop alive, so they're closed
+    // if they aren't reused for a while.
+    entry.timer = setTimeout(entry.evict, this.#keepAliveTimeout);
+
+    socket.on('error', entry.evict);
+    socket.on('end', entry.drop);
+    socket.setTimeout(0);
+    entries.push(entry);
+    this.#idle.set(key, entries);
+  }
+
+  /**
+   * Closes all the idle connections of the pool.
+   */
+  close() {
+    for (const entries of this.#idle.values()) {
+      entries.slice().forEach((entry) => entry.evict());
+    }
+  }
+}
+
 const urlRegex = new RegExp('^(.*:)//([A-Za-z0-9-.]+)(:[0-9]+)?(.*)$');
 
 /**
@@ -220,6 +317,9 @@ class Request {
   #headers;
   #isChunkedEncoding;
   #signal;
+  #pool;
+  #onAbort;
+  #responded;
 
   constructor(url, options) {
     // Include protocol in URL.
@@ -236,6 +336,7 @@ class Request {
     this.#path = path || '/';
     this.#method = options.method.toUpperCase();
     this.#signal = options.signal;
+    this.#pool = options.pool;
 
     // Check if HTTP method is valid.
     if (!METHODS.includes(this.#method)) {
@@ -259,7 +360,7 @@ class Request {
     this.#headers.set('host', this.#hostname + ':' + this.#port);
     this.#headers.set('user-agent', `dune/${process.version}`);
     this.#headers.set('accept', '*/*');
-    this.#headers.set('connection', 'close');
+    this.#headers.set('connection', this.#pool ? 'keep-alive' : 'close');
     this.#headers.set('content-length', this.#bodyLength);
 
     // Check if encoding should be chunked.
@@ -278,10 +379,43 @@ class Request {
       this.#headers.set(name.toLowerCase(), value);
     }
 
-    this.#socket = new net.Socket();
+    // Reuse an idle connection to the same host, if available.
+    this.#socket =
+      this.#pool?.acquire(this.#hostname, this.#port) ?? new net.Socket();
   }
 
   async send() {
+    // Subscribe to the abort-controller if provided.
+    if (this.#signal) {
+      this.#onAbort = () => this.#socket.destroy();
+      this.#signal.addEventListener('abort', this.#onAbort);
+    }
+
+    // Note: Streamed bodies can't be sent again, and responses (even partial
+    // ones) are never dropped.
+    const reused = this.#socket.remotePort !== undefined;
+    const canRetry = () =>
+      reused &&
+      !this.#isChunkedEncoding &&
+      !this.#responded &&
+      !this.#signal?.aborted;
+
+    try {
+      const response = await this.#exchange();
+      if (response || !canRetry()) return response;
+    } catch (err) {
+      if (!canRetry()) throw err;
+    }
+
+    // Note: A reused connection may have been closed by the remote host in
+    // the meantime, so the request is retried (once) on a new connection.
+    this.#socket.destroy();
+    this.#socket = new net.Socket();
+    return this.#exchange();
+  }
+
+  // Writes the request to the socket, and waits for the response headers.
+  async #exchange() {
     // Start building the HTTP message.
     const encoder = new TextEncoder();
     const reqHeaders = [`${this.#method} ${this.#path} HTTP/1.1`];
@@ -295,18 +429,15 @@ class Request {
     const reqHeadersString = reqHeaders.join('\r\n');
     const reqHeadersBytes = encoder.encode(`${reqHeadersString}\r\n\r\n`);
 
-    // Write headers to the socket.
-    await this.#socket.connect(this.#port, this.#hostname);
-    await this.#socket.write(reqHeadersBytes);
-
-    // Subscribe to the abort-controller if provided.
-    if (this.#signal) {
-      this.#signal.addEventListener('abort', () => this.#socket.destroy());
+    // Write headers to the socket (connecting it, unless it's reused).
+    if (this.#socket.remotePort === undefined) {
+      await this.#socket.connect(this.#port, this.#hostname);
     }
+    await this.#socket.write(reqHeadersBytes);
 
     // Write body to the socket (sized).
     if (this.#body && !this.#isChunkedEncoding) {
-      this.#socket.write(this.#body);
+      await this.#socket.write(this.#body);
     }
 
     // Write body to the socket (chunked).
@@ -327,6 +458,8 @@ class Request {
     let buffer = new Uint8Array();
 
     for await (const data of wrapIterable(this.#socket)) {
+      this.#responded = true;
+
       // Concatenate existing buffer with new data.
       buffer = concatUint8Arrays(buffer, data);
       const metadata = binding.parseResponse(buffer);
@@ -336,6 +469,7 @@ class Request {
 
       // Check status code and throw if requested.
       if (metadata.statusCode >= 400 && this.#throwOnError) {
+        this.#socket.end();
         const message = STATUS_CODES[metadata.statusCode];
         throw new Error(`HTTP request failed with error: "${message}"`);
       }
@@ -343,9 +477,35 @@ class Request {
       // Remove headers data from buffer.
       buffer = buffer.subarray(metadata.marker);
 
-      return new IncomingResponse(metadata, buffer, this.#socket);
+      const release = this.#canReuse(metadata)
+        ? () => this.#release()
+        : undefined;
+
+      return new IncomingResponse(metadata, buffer, this.#socket, release);
     }
   }
+
+  // Checks if the connection can be reused once the response is consumed,
+  // which requires knowing where its body ends.
+  #canReuse({ version, headers }) {
+    const connection = headers['connection']?.toLowerCase();
+    const isChunked = headers['transfer-encoding']?.includes('chunked');
+    const isSized = headers['content-length'] !== undefined;
+
+    return (
+      Boolean(this.#pool) &&
+      version === 1 &&
+      connection !== 'close' &&
+      this.#method !== 'HEAD' &&
+      (isChunked || isSized)
+    );
+  }
+
+  // Hands the socket back to the pool, once the response has been consumed.
+  #release() {
+    this.#signal?.removeEventListener('abort', this.#onAbort);
+    this.#pool.release(this.#hostname, this.#port, this.#socket);
+  }
 }
 
 /**
@@ -356,10 +516,10 @@ class IncomingResponse {
   #headers;
   #body;
 
-  constructor(metadata, buffer, socket) {
+  constructor(metadata, buffer, socket, release) {
     this.#statusCode = metadata.statusCode;
     this.#headers = metadata.headers;
-    this.#body = new Body(metadata, buffer, socket, false);
+    this.#body = new Body(metadata, buffer, socket, false, release);
   }
 
   /**
@@ -400,8 +560,10 @@ class Body {
   #isChunked;
   #isComplete;
   #keepAlive;
+  #release;
+  #encoding;
 
-  constructor({ headers }, buffer, socket, keepAlive = true) {
+  constructor({ headers }, buffer, socket, keepAlive = true, release) {
     this.#body = buffer;
     this.#bodyLength = Number.parseInt(headers['content-length']) || 0;
     this.#isChunked = headers['transfer-encoding']?.includes('chunked');
@@ -409,8 +571,12 @@ class Body {
     this.#keepAlive = keepAlive;
     this.#socket = socket;
 
+    // Note: Pooled connections are released (instead of closed) once the
+    // body has been fully received.
+    this.#release = release ?? (() => this.#socket.end());
+
     if (this.#isComplete && !this.#isChunked && !keepAlive) {
-      this.#socket.end();
+      this.#release();
       this.#socket = undefined;
     }
   }
@@ -421,13 +587,14 @@ class Body {
    * @returns {Promise<string>} The complete body as a UTF-8 string.
    */
   async text() {
-    const string = [];
-    const decoder = new TextDecoder();
+    // Note: The chunks are joined before decoding, since a multi-byte
+    // character may be split between two of them.
+    const chunks = [];
     const asyncIterator = this[Symbol.asyncIterator]();
     for await (const chunk of asyncIterator) {
-      string.push(decoder.decode(chunk));
+      chunks.push(chunk);
     }
-    return string.join('');
+    return new TextDecoder().decode(concatUint8Arrays(...chunks));
   }
 
   /**
@@ -440,11 +607,50 @@ class Body {
     return JSON.parse(data);
   }
 
+  /**
+   * Decompresses the body (encoded with the given content-encoding) while
+   * it's being read.
+   *
+   * @param {string} encoding - The content-encoding of the body.
+   * @returns {Body} The same body instance.
+   * @ignore
+   */
+  decompress(encoding) {
+    this.#encoding = encoding;
+    return this;
+  }
+
   /**
    * The HTTP body should be async iterable.
    * @ignore
    */
   async *[Symbol.asyncIterator](signal) {
+    if (!this.#encoding) {
+      yield* this.#read(signal);
+      return;
+    }
+
+    const decoder = binding.createDecoder(this.#encoding);
+    const decode = (chunk) => new Uint8Array(binding.decode(decoder, chunk));
+
+    try {
+      for await (const chunk of this.#read(signal)) {
+        const data = decode(new Uint8Array(chunk));
+        if (data.length > 0) yield data;
+      }
+
+      // Note: The decoder is finished once the whole body has been received,
+      // failing if it was truncated or corrupted.
+      const data = new Uint8Array(binding.finishDecoder(decoder));
+      if (data.length > 0) yield data;
+    } finally {
+      // Note: Frees the decoder if the body is abandoned part-way.
+      binding.releaseDecoder(decoder);
+    }
+  }
+
+  // Yields the raw chunks of the body as they're received.
+  async *#read(signal) {
     // Close socket on stream pipeline errors.
     if (signal) signal.on('uncaughtStreamException', () => this.#socket.end());
 
@@ -455,23 +661,19 @@ class Body {
       return;
     }
 
-    // TODO: Check if chunks are available from the start.
-    // Node.js for example combines the first chunk with the HTTP headers when
-    // sending responses with chunked encoding.
+    // Note: Chunks may arrive along with the HTTP headers (Node.js for example
+    // combines the first chunk with them), so the buffered ones come first.
+    if (this.#isChunked && (yield* this.#extractChunks())) {
+      if (!this.#keepAlive) this.#release();
+      return;
+    }
 
     for await (const newData of wrapIterable(this.#socket)) {
       // Mix current body with new data.
       this.#body = concatUint8Arrays(this.#body, newData);
 
       if (this.#isChunked) {
-        // Try extracting available chunks.
-        const result = binding.parseChunks(this.#body);
-        // No results means not enough bytes to extract the next chunk.
-        if (result) {
-          this.#body = this.#body.subarray(result.position);
-          yield* result.chunks;
-          if (result.done) break;
-        }
+        if (yield* this.#extractChunks()) break;
       } else {
         // Note: The following code handles the case when the HTTP's body
         // length is already known from the `Content-Length` header
@@ -485,7 +687,19 @@ class Body {
     }
 
     // Close TCP socket on not keep-alive connections.
-    if (!this.#keepAlive) this.#socket.end();
+    if (!this.#keepAlive) this.#release();
+  }
+
+  // Yields the available chunks of the body, returning whether the last
+  // (empty) chunk has been received.
+  *#extractChunks() {
+    // No results means not enough bytes to extract the next chunk.
+    const result = binding.parseChunks(this.#body);
+    if (!result) return false;
+
+    this.#body = this.#body.subarray(result.position);
+    yield* result.chunks;
+    return result.done;
   }
 }
 
@@ -550,7 +764,7 @@ export class Server extends EventEmitter {
       await this.#handleConnection(socket);
     } catch (err) {
       // Don't crash the server for a single misbehaving socket.
-      if (err?.code !== 'ERR_CONNECTION_RESET') {
+      if (err?.code !== 'ECONNRESET') {
         throw err;
       }
     }
@@ -1040,6 +1254,7 @@ const defaultOptions = {
  * @param {Number} [options.timeout] - A timeout in milliseconds for the request.
  * @param {boolean} [options.throwOnError] - Will throw an error for non-2xx response codes.
  * @param {AbortSignal} [options.signal] - An AbortSignal to cancel the request.
+ * @param {ConnectionPool} [options.pool] - A pool to reuse (keep-alive) connections from.
  * @returns {Promise<IncomingResponse>} Containing the HTTP response.
  */
 export function request(url, options = {}) {
@@ -1051,7 +1266,7 @@ export function request(url, options = {}) {
   // Check if the operation has been already aborted.
   options?.signal?.throwIfAborted();
 
-  const configuration = Object.assign(defaultOptions, options);
+  const configuration = Object.assign({}, defaultOptions, options);
   const request = new Request(url, configuration);
   const { signal } = configuration;
 
@@ -1078,4 +1293,11 @@ export function createServer(onRequest) {
   return server;
 }
 
-export default { METHODS, STATUS_CODES, Server, createServer, request };
+export default {
+  METHODS,
+  STATUS_CODES,
+  ConnectionPool,
+  Server,
+  createServer,
+  request,
+};
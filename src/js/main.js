@@ -1,8 +1,10 @@
 import process from 'process';
 import timers from 'timers';
+import { bindAsyncContext } from 'util';
 import fetch from '@web/fetch';
 import structuredClone from '@web/clone';
 import { Console, prompt, wrapConsole } from 'console';
+import { Event, EventTarget } from '@web/event_target';
 import { AbortController, AbortSignal } from '@web/abort';
 import { TextEncoder, TextDecoder } from '@web/text_encoding';
 
@@ -26,9 +28,12 @@ function queueMicrotask(callback) {
     throw new TypeError(`The "callback" argument must be of type function.`);
   }
 
+  // Note: The callback runs under the async context it was queued from.
+  const task = bindAsyncContext(callback);
+
   $$queueMicro(() => {
     try {
-      callback();
+      task();
     } catch (err) {
       reportError(err);
     }
@@ -57,6 +62,19 @@ makeGlobal('clearImmediate', timers.clearImmediate);
 makeGlobal('TextEncoder', TextEncoder);
 makeGlobal('TextDecoder', TextDecoder);
 makeGlobal('structuredClone', structuredClone);
+makeGlobal('Event', Event);
+makeGlobal('EventTarget', EventTarget);
 makeGlobal('AbortController', AbortController);
 makeGlobal('AbortSignal', AbortSignal);
 makeGlobal('fetch', fetch);
+
+// Note: The global object is an event target as well.
+const eventTargetMethods = [
+  'addEventListener',
+  'removeEventListener',
+  'dispatchEvent',
+];
+
+for (const name of eventTargetMethods) {
+  makeGlobal(name, EventTarget.prototype[name].bind(globalThis));
+}
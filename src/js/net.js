@@ -10,10 +10,11 @@
  */
 
 import dns from 'dns';
-import assert from 'assert';
+import { Assert as assert } from 'assert';
 import { EventEmitter } from 'events';
+import { trackBindingOps } from 'util';
 
-const binding = process.binding('net');
+const binding = trackBindingOps(process.binding('net'), 'net');
 
 function parseOptionsArgs(args) {
   // Use options overloading.
@@ -9,4 +9,126 @@ const perfHooks = process.binding('perf_hooks');
 
 export const performance = perfHooks.performance;
 
+// Resolves a mark name (or a timestamp) into a timestamp.
+function toTimestamp(value) {
+  if (typeof value === 'number') return value;
+
+  const marks = perfHooks.getEntries(String(value), 'mark');
+  if (marks.length === 0) {
+    throw new SyntaxError(`The "${value}" performance mark has not been set.`);
+  }
+
+  return marks.at(-1).startTime;
+}
+
+/**
+ * Creates a new mark entry in the performance timeline.
+ *
+ * @param {String} name - The name of the mark.
+ * @param {Object} [options] - The mark's `startTime` and `detail` (optional).
+ * @returns {Object} The created mark entry.
+ */
+performance.mark = function mark(name, options = {}) {
+  if (arguments.length === 0) {
+    throw new TypeError('The "name" argument must be specified.');
+  }
+
+  const { startTime = performance.now(), detail } = options ?? {};
+  return perfHooks.addEntry(String(name), 'mark', startTime, 0, detail);
+};
+
+/**
+ * Creates a new measure entry between two marks (or timestamps).
+ *
+ * @param {String} name - The name of the measure.
+ * @param {(String|Object)} [startOrOptions] - The start mark, or the measure's `start`, `end`, `duration` and `detail`.
+ * @param {String} [endMark] - The end mark (defaults to the current time).
+ * @returns {Object} The created measure entry.
+ */
+performance.measure = function measure(name, startOrOptions, endMark) {
+  if (arguments.length === 0) {
+    throw new TypeError('The "name" argument must be specified.');
+  }
+
+  const options =
+    typeof startOrOptions === 'object' && startOrOptions !== null
+      ? startOrOptions
+      : { start: startOrOptions, end: endMark };
+
+  const { start, end, duration, detail } = options;
+  const endTime =
+    end !== undefined
+      ? toTimestamp(end)
+      : duration !== undefined && start !== undefined
+        ? toTimestamp(start) + duration
+        : performance.now();
+
+  const startTime =
+    start !== undefined
+      ? toTimestamp(start)
+      : duration !== undefined
+        ? endTime - duration
+        : 0;
+
+  const elapsed = endTime - startTime;
+  return perfHooks.addEntry(name, 'measure', startTime, elapsed, detail);
+};
+
+/**
+ * Returns all the entries of the performance timeline, in chronological order.
+ *
+ * @returns {Object[]}
+ */
+performance.getEntries = function getEntries() {
+  return perfHooks.getEntries();
+};
+
+/**
+ * Returns the entries with the given name (and optionally type).
+ *
+ * @param {String} name - The name of the entries.
+ * @param {String} [type] - The type of the entries (e.g. `mark`).
+ * @returns {Object[]}
+ */
+performance.getEntriesByName = function getEntriesByName(name, type) {
+  return perfHooks.getEntries(String(name), type);
+};
+
+/**
+ * Returns the entries of the given type (e.g. `mark` or `measure`).
+ *
+ * @param {String} type - The type of the entries.
+ * @returns {Object[]}
+ */
+performance.getEntriesByType = function getEntriesByType(type) {
+  return perfHooks.getEntries(undefined, String(type));
+};
+
+/**
+ * Removes the mark entries (all of them, or only those with the given name).
+ *
+ * @param {String} [name] - The name of the marks to remove.
+ */
+performance.clearMarks = function clearMarks(name) {
+  perfHooks.clearEntries('mark', name);
+};
+
+/**
+ * Removes the measure entries (all of them, or only those with the given name).
+ *
+ * @param {String} [name] - The name of the measures to remove.
+ */
+performance.clearMeasures = function clearMeasures(name) {
+  perfHooks.clearEntries('measure', name);
+};
+
+/**
+ * Removes the resource timing entries.
+ *
+ * Note: Resource timings are not recorded yet, so this has no effect for now.
+ */
+performance.clearResourceTimings = function clearResourceTimings() {
+  perfHooks.clearEntries('resource');
+};
+
 export default perfHooks;
@@ -1,11 +1,12 @@
 import { EventEmitter } from 'events';
-import { cloneFunction as clone } from 'util';
+import { cloneFunction as clone, bindAsyncContext } from 'util';
 
 const cache = new Map();
 
 const internalBinding = clone(process.binding);
 const kill = clone(process.kill);
 const nextTick = clone(process.nextTick);
+const cpuUsage = clone(process.cpuUsage);
 
 // Note: Integrating a caching layer into process.binding enables us
 // to avoid traversing the JavaScript - Rust bridge for native method
@@ -29,12 +30,24 @@ process.kill = (pid, signal = 'SIGKILL') => {
   kill(pid, signal);
 };
 
+process.cpuUsage = (previousValue) => {
+  const usage = cpuUsage();
+  if (!previousValue) return usage;
+
+  // Return the difference from a previous reading.
+  return {
+    user: usage.user - previousValue.user,
+    system: usage.system - previousValue.system,
+  };
+};
+
 process.nextTick = (callback, ...args) => {
   // Check if callback is a valid function.
   if (typeof callback !== 'function') {
     throw new TypeError(`The "callback" argument must be of type function.`);
   }
-  nextTick(() => callback(...args));
+  const task = bindAsyncContext(callback);
+  nextTick(() => task(...args));
 };
 
 function defineStream(name, getter) {
@@ -47,17 +60,49 @@ function defineStream(name, getter) {
 
 const io = process.binding('stdio');
 
+// Adapts a write binding into a writable stream of bytes.
+function createWritable(write) {
+  return {
+    bytesWritten: 0,
+    write(chunk) {
+      // Check the chunk argument type.
+      if (!(chunk instanceof Uint8Array)) {
+        throw new TypeError(`The "chunk" argument must be a Uint8Array.`);
+      }
+      write(chunk);
+      this.bytesWritten += chunk.byteLength;
+    },
+    end() {},
+  };
+}
+
+// A readable (async iterable) stream of the bytes coming from stdin.
+const stdinReadable = {
+  async *[Symbol.asyncIterator]() {
+    let chunk;
+    while ((chunk = io.readBytes()) !== null) {
+      yield new Uint8Array(chunk);
+    }
+  },
+};
+
+const stdoutWritable = createWritable(io.write);
+const stderrWritable = createWritable(io.writeError);
+
 defineStream('stdout', () => ({
   write: io.write,
   end() {},
+  writable: stdoutWritable,
 }));
 
 defineStream('stdin', () => ({
   read: io.read,
+  readable: stdinReadable,
 }));
 
 defineStream('stderr', () => ({
   write: io.writeError,
+  writable: stderrWritable,
 }));
 
 const os = process.binding('signals');
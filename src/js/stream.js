@@ -10,7 +10,7 @@
  * @module Stream
  */
 
-import assert from 'assert';
+import { Assert as assert } from 'assert';
 import { EventEmitter } from 'events';
 
 const isFunction = (value) => typeof value === 'function';
@@ -96,10 +96,39 @@ export function compose(...targets) {
   };
 }
 
+/**
+ * A Duplex stream that decodes a (byte) stream into its lines, handling lines
+ * split across chunks, a trailing partial line and CRLF line endings.
+ *
+ * @param {AsyncIterable} source - The source stream of bytes (or strings).
+ * @returns {AsyncGenerator<String>} The decoded lines (without line endings).
+ */
+export async function* lines(source) {
+  const decoder = new TextDecoder();
+  const trimCR = (line) => (line.endsWith('\r') ? line.slice(0, -1) : line);
+  let buffered = '';
+
+  for await (const chunk of source) {
+    // Note: Multi-byte characters may be split across (byte) chunks.
+    buffered +=
+      typeof chunk === 'string'
+        ? chunk
+        : decoder.decode(chunk, { stream: true });
+
+    const parts = buffered.split('\n');
+    buffered = parts.pop();
+    yield* parts.map(trimCR);
+  }
+
+  // Flush any bytes (and partial line) left when the stream ended.
+  buffered += decoder.decode();
+  if (buffered) yield trimCR(buffered);
+}
+
 /**
  * An alias of `pipeline()`.
  * @ignore
  */
 export const pipe = pipeline;
 
-export default { pipeline, compose, pipe };
+export default { pipeline, compose, pipe, lines };
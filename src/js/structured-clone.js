@@ -70,8 +70,18 @@ const deserializer = ($, _) => {
         return set;
       }
       case ERROR: {
-        const { name, message } = value;
-        return as(new env[name](message), index);
+        const { name, message, stack, entries = [] } = value;
+        const error = as(new env[name](message), index);
+        if (stack !== undefined) error.stack = stack;
+        if ('cause' in value) {
+          Object.defineProperty(error, 'cause', {
+            value: unpair(value.cause),
+            writable: true,
+            configurable: true,
+          });
+        }
+        for (const [key, index] of entries) error[unpair(key)] = unpair(index);
+        return error;
       }
       case BIGINT:
         // eslint-disable-next-line no-undef
@@ -129,6 +139,18 @@ const typeOf = (value) => {
   return [OBJECT, asString];
 };
 
+const ERROR_NAMES = [
+  'Error',
+  'EvalError',
+  'RangeError',
+  'ReferenceError',
+  'SyntaxError',
+  'TypeError',
+  'URIError',
+];
+
+const ERROR_KEYS = ['name', 'message', 'stack', 'cause'];
+
 const shouldSkip = ([TYPE, type]) =>
   TYPE === PRIMITIVE && (type === 'function' || type === 'symbol');
 
@@ -216,8 +238,21 @@ const serializer = (strict, json, $, _) => {
       }
     }
 
-    const { message } = value;
-    return as([TYPE, { name: type, message }], value);
+    // Note: Errors are cloned as one of the native error types (falling back
+    // to `Error`), keeping their stack, cause and other own properties.
+    const name = ERROR_NAMES.includes(value.name) ? value.name : 'Error';
+    const error = { name, message: String(value.message), stack: value.stack };
+    const index = as([TYPE, error], value);
+
+    if (Object.hasOwn(value, 'cause')) error.cause = pair(value.cause);
+
+    error.entries = [];
+    for (const key of keys(value)) {
+      if (ERROR_KEYS.includes(key)) continue;
+      if (strict || !shouldSkip(typeOf(value[key])))
+        error.entries.push([pair(key), pair(value[key])]);
+    }
+    return index;
   };
 
   return pair;
@@ -10,16 +10,98 @@
  */
 
 import fs from 'fs';
+import { counter } from 'assert';
 import { performance } from 'perf_hooks';
-import { bg_green, bg_red, red, green, bold } from 'colors';
+import { inspect, setConsoleSink } from 'console';
+import {
+  activeResources,
+  getContextValue,
+  originalPosition,
+  pendingOps,
+  runWithContextValue,
+  setOpTracing,
+  setOpTracking,
+  setResourceTracking,
+} from 'util';
+import {
+  bg_green,
+  bg_red,
+  bg_yellow,
+  red,
+  green,
+  yellow,
+  bright_black,
+  bold,
+} from 'colors';
 
 // Output labels.
 const OK = bg_green(bold(' OK '));
 const FAIL = bg_red(bold(' FAIL '));
+const CANCELLED = bg_yellow(bold(' CANCELLED '));
+const IGNORED = bg_yellow(bold(' IGNORED '));
 
 // Regex to match test files.
 const TEST_FILE = new RegExp(/.*.spec.ts$|.*.test.ts$|.*.spec.js$|.*.test.js$/);
 
+// Resolves why a test (or benchmark) is ignored, returning `null` if it should
+// run. The `ignore` option can be a boolean, the platforms to skip it on (as
+// in `process.platform`), or a function returning either of them.
+function ignoreReason({ ignore, reason }) {
+  if (typeof ignore === 'function') ignore = ignore();
+
+  if (typeof ignore === 'string' || Array.isArray(ignore)) {
+    const platforms = [ignore].flat();
+    if (!platforms.includes(process.platform)) return null;
+    return reason ?? `on ${process.platform}`;
+  }
+
+  return ignore ? reason ?? '' : null;
+}
+
+// Async context key of the currently running test.
+const CURRENT_TEST = Symbol('currentTest');
+
+// Async context key of the buffer collecting the output of a parallel test.
+const OUTPUT_BUFFER = Symbol('outputBuffer');
+
+// Attributes console output to the test that produced it (the output is
+// still printed as usual).
+const writeOutput = setConsoleSink((stream, output) => {
+  emitOutput(stream, output, currentTest());
+});
+
+// Attributes (and prints) console output, unless it's being buffered so the
+// output of tests running in parallel doesn't interleave.
+//
+// Note: Output of the tests running within a buffered batch is attributed to
+// them right away, but the output of the test running the batch (e.g. the
+// reports) only once it's flushed, so it's kept in the same order.
+function emitOutput(stream, output, test) {
+  const buffer = getContextValue(OUTPUT_BUFFER);
+
+  if (test && test !== buffer?.owner) {
+    test.output.push({ stream, output });
+    test = undefined;
+  }
+
+  if (buffer) {
+    buffer.entries.push([stream, output, test]);
+    return;
+  }
+
+  writeOutput(stream, output);
+}
+
+/**
+ * Returns the context of the test running in the current async context
+ * (e.g. its `name`, `file` and captured console `output`).
+ *
+ * @returns {Object|undefined}
+ */
+export function currentTest() {
+  return getContextValue(CURRENT_TEST);
+}
+
 // Error type referring to test duration timeout.
 export class TimeoutError extends Error {
   constructor(message) {
@@ -47,6 +129,110 @@ function timeout(promise, time = 0) {
   });
 }
 
+// Throws if resources opened after the snapshot was taken are still open.
+function checkLeakedResources(snapshot) {
+  const leaks = [...activeResources()]
+    .filter(([resource]) => !snapshot.has(resource))
+    .map(([, { description, stack }]) => {
+      const trace = stack.replace(/^\s*/gm, '    ');
+      return (
+        `  - ${description} was opened during the test, ` +
+        `but not closed during the test. Opened at:\n${trace}`
+      );
+    });
+
+  if (leaks.length > 0) {
+    throw new Error(`Test leaked resources:\n${leaks.join('\n')}`);
+  }
+}
+
+// Throws if async ops started after the snapshot was taken are still pending.
+function checkLeakedOps(snapshot) {
+  const leaks = [...pendingOps()]
+    .filter(([op]) => !snapshot.has(op))
+    .map(([, { description, stack }]) => {
+      const message =
+        `  - ${description} was started during the test, ` +
+        `but never completed during the test.`;
+      // Note: Stack traces are only captured when op tracing is enabled.
+      return stack
+        ? `${message} Started at:\n${stack.replace(/^\s*/gm, '    ')}`
+        : `${message} Enable op tracing (\`--trace-ops\`) to see where.`;
+    });
+
+  if (leaks.length > 0) {
+    throw new Error(`Test leaked async ops:\n${leaks.join('\n')}`);
+  }
+}
+
+// Drives to completion iterators returned by (async) generator tests.
+async function drive(result) {
+  const isIterator =
+    typeof result?.next === 'function' &&
+    (result[Symbol.asyncIterator] || result[Symbol.iterator]);
+
+  if (!isIterator) return result;
+
+  // eslint-disable-next-line no-unused-vars
+  for await (const _ of result) {
+    // We only need to exhaust the iterator.
+  }
+}
+
+// Extracts the file locations from an error's stack trace.
+function stackFiles(error) {
+  return error.stack
+    .split('\n')
+    .slice(1)
+    .map((line) => line.match(/at (?:.* \()?(.*?):\d+:\d+\)?$/))
+    .filter(Boolean)
+    .map((match) => match[1]);
+}
+
+// The location of the current module (as it appears in stack traces).
+const SELF = stackFiles(new Error())[0];
+
+// Returns the location of the module that registered a test.
+function callerFile() {
+  return stackFiles(new Error()).find((file) => file !== SELF);
+}
+
+// Returns the location of the call (in the test's file) that threw the error,
+// mapped back to the original source when the file was transpiled.
+function failureLocation(error, file) {
+  const frame = String(error?.stack ?? '')
+    .split('\n')
+    .slice(1)
+    .map((line) => line.match(/at (?:.* \()?(.*?):(\d+):(\d+)\)?$/))
+    .find((match) => match && match[1] === file);
+
+  if (!frame) return null;
+
+  const [, path, line, column] = frame;
+  const position = originalPosition(path, Number(line), Number(column));
+  return `${relativePath(path)}:${position.line}:${position.column}`;
+}
+
+// Returns the name filter set through the `DUNE_TEST_FILTER` environment
+// variable (either as a pattern, or as a `/pattern/flags` literal).
+function envFilter() {
+  const value = process.env.DUNE_TEST_FILTER;
+  if (!value) return undefined;
+
+  const literal = value.match(/^\/(.*)\/([a-z]*)$/);
+  try {
+    return literal ? new RegExp(literal[1], literal[2]) : new RegExp(value);
+  } catch (err) {
+    throw new SyntaxError(`Invalid DUNE_TEST_FILTER pattern: ${err.message}`);
+  }
+}
+
+// Returns a path relative to the current working directory (if possible).
+function relativePath(path) {
+  const cwd = process.cwd();
+  return path.startsWith(cwd) ? `.${path.slice(cwd.length)}` : path;
+}
+
 // Utility function to join paths similar to Node.js.
 function joinPaths(...parts) {
   const separator = '/';
@@ -54,6 +240,186 @@ function joinPaths(...parts) {
   return parts.join(separator).replace(replace, separator);
 }
 
+// Escapes a value to be used as XML text (or attribute value).
+function escapeXml(value) {
+  const entities = {
+    '&': '&amp;',
+    '<': '&lt;',
+    '>': '&gt;',
+    '"': '&quot;',
+    "'": '&apos;',
+  };
+  return String(value)
+    .replace(/[\u0000-\u0008\u000b\u000c\u000e-\u001f]/g, '')
+    .replace(/[&<>"']/g, (char) => entities[char]);
+}
+
+/**
+ * Formats the results of a test run as a JUnit XML report.
+ *
+ * @param {Object[]} results - The results of a test runner (see `runner.results`).
+ * @param {String} [name] - The name of the report (default: `dune`).
+ * @returns {String}
+ */
+export function toJUnit(results, name = 'dune') {
+  const seconds = (duration) => (duration / 1000).toFixed(3);
+  const summary = (entries) => {
+    const skipped = entries.filter((r) => r.cancelled || r.ignored).length;
+    const failures = entries.filter((r) => !r.ok).length - skipped;
+    const time = entries.reduce((total, r) => total + r.duration, 0);
+    return (
+      `tests="${entries.length}" failures="${failures}" ` +
+      `skipped="${skipped}" time="${seconds(time)}"`
+    );
+  };
+
+  const lines = [
+    '<?xml version="1.0" encoding="UTF-8"?>',
+    `<testsuites name="${escapeXml(name)}" ${summary(results)}>`,
+  ];
+
+  // Every test file is reported as a test suite.
+  const files = [...new Set(results.map((result) => result.file))];
+
+  for (const file of files) {
+    const suite = escapeXml(file ? relativePath(file) : name);
+    const entries = results.filter((result) => result.file === file);
+    lines.push(`  <testsuite name="${suite}" ${summary(entries)}>`);
+
+    for (const result of entries) {
+      const testcase =
+        `    <testcase name="${escapeXml(result.description)}" ` +
+        `classname="${suite}" time="${seconds(result.duration)}"`;
+
+      if (result.ok) {
+        lines.push(`${testcase} />`);
+        continue;
+      }
+
+      lines.push(`${testcase}>`);
+
+      if (result.cancelled) {
+        lines.push('      <skipped message="Cancelled" />');
+      } else if (result.ignored) {
+        const message = escapeXml(result.reason || 'Ignored');
+        lines.push(`      <skipped message="${message}" />`);
+      } else {
+        const { error } = result;
+        const message = escapeXml(error?.message ?? String(error));
+        const type = escapeXml(error?.name ?? 'Error');
+        const details = escapeXml(error?.stack ?? String(error));
+        lines.push(
+          `      <failure message="${message}" type="${type}">` +
+            `${details}</failure>`
+        );
+      }
+
+      lines.push('    </testcase>');
+    }
+
+    lines.push('  </testsuite>');
+  }
+
+  lines.push('</testsuites>');
+  return `${lines.join('\n')}\n`;
+}
+
+// Describes how a benchmark compares to its group's baseline.
+function compareToBaseline(result, baseline) {
+  if (result === baseline) return bright_black('(baseline)');
+
+  const ratio = result.average / baseline.average;
+  return ratio >= 1
+    ? red(`${ratio.toFixed(2)}x slower`)
+    : green(`${(1 / ratio).toFixed(2)}x faster`);
+}
+
+/**
+ * Formats the results of the benchmarks as a report, comparing the benchmarks
+ * of each group against its baseline (or the fastest one, if none is set).
+ *
+ * @param {Object[]} results - The results of a runner's benchmarks.
+ * @returns {String}
+ */
+export function benchReport(results) {
+  const width = Math.max(...results.map(({ name }) => name.length));
+  const groups = new Map();
+
+  for (const result of results) {
+    const group = result.group ?? null;
+    if (!groups.has(group)) groups.set(group, []);
+    groups.get(group).push(result);
+  }
+
+  const lines = [];
+
+  for (const [group, entries] of groups) {
+    const fastest = entries.reduce((a, b) => (b.average < a.average ? b : a));
+    const baseline = entries.find((result) => result.baseline) ?? fastest;
+
+    if (group !== null) lines.push(bold(`group ${group}`));
+
+    for (const result of entries) {
+      const time = `${result.average.toFixed(3)} ms/iter`;
+      const comparison =
+        group !== null ? ` ${compareToBaseline(result, baseline)}` : '';
+      lines.push(`${result.name.padEnd(width)}  ${yellow(time)}${comparison}`);
+    }
+  }
+
+  return lines.join('\n');
+}
+
+// Creates a seeded pseudo-random number generator (mulberry32).
+function createRandom(seed) {
+  let state = seed >>> 0;
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+/**
+ * Shuffles a list of items, always producing the same order for the same seed.
+ *
+ * @param {Array} items - The items to be shuffled.
+ * @param {Number} seed - The seed used for the shuffling.
+ * @returns {Array} A new shuffled array.
+ */
+export function shuffle(items, seed) {
+  const random = createRandom(seed);
+  const output = [...items];
+
+  // Fisher–Yates shuffle algorithm.
+  for (let i = output.length - 1; i > 0; i--) {
+    const j = Math.floor(random() * (i + 1));
+    [output[i], output[j]] = [output[j], output[i]];
+  }
+
+  return output;
+}
+
+// Maps the items using an async function, running at most `limit` at a time.
+async function pool(items, limit, fn) {
+  const results = [];
+  let next = 0;
+
+  const worker = async () => {
+    while (next < items.length) {
+      const index = next++;
+      results[index] = await fn(items[index]);
+    }
+  };
+
+  const workers = Array.from({ length: Math.min(limit, items.length) }, worker);
+  await Promise.all(workers);
+
+  return results;
+}
+
 /**
  *  TestRunner is the main executor to run JavaScript tests.
  */
@@ -61,13 +427,29 @@ export class TestRunner {
   // Initializes the test runner.
   constructor() {
     this.tests = new Map();
+    this.benches = new Map();
     this.testFiles = [];
     this.filter = undefined;
+    this.fileFilter = undefined;
     this.failFast = false;
+    this.shuffle = undefined;
+    this.concurrency = 1;
+    this.slowThreshold = 1000;
+    this.traceOps = false;
+    this.junit = undefined;
+    this.results = [];
     this.counters = {
       ok: 0,
       failed: 0,
       ignored: 0,
+      filtered: 0,
+      cancelled: 0,
+    };
+    this.hooks = {
+      beforeAll: [],
+      afterAll: [],
+      beforeEach: [],
+      afterEach: [],
     };
   }
 
@@ -83,9 +465,395 @@ export class TestRunner {
       throw new Error("Tests can't share the same description.");
     }
 
+    // Tag the test with the module it was registered from.
+    testFn.file = testFn.file ?? callerFile();
+
     this.tests.set(description, testFn);
   }
 
+  /**
+   * Registers a new benchmark to the runner.
+   *
+   * @param {String} name - The name of the benchmark.
+   * @param {Function} benchFn - The function to measure.
+   */
+  bench(name, benchFn) {
+    if (this.benches.has(name)) {
+      throw new Error("Benchmarks can't share the same name.");
+    }
+
+    this.benches.set(name, benchFn);
+  }
+
+  /**
+   * Runs the registered benchmarks and reports their timings.
+   *
+   * @returns {Promise<Object[]>} The results (with the average time of an iteration).
+   */
+  async runBenchmarks() {
+    const filter = this.filter ?? envFilter();
+    const results = [];
+
+    for (const [name, benchFn] of this.benches) {
+      if (filter && !filter.test(name)) continue;
+      if (ignoreReason(benchFn) !== null) continue;
+
+      const { group, baseline = false, iterations = 100 } = benchFn;
+
+      // Note: A few warm-up runs let V8 optimize the function first.
+      for (let i = 0; i < Math.min(iterations, 10); i++) await benchFn();
+
+      const startTime = performance.now();
+      for (let i = 0; i < iterations; i++) await benchFn();
+      const average = (performance.now() - startTime) / iterations;
+
+      results.push({ name, group, baseline, iterations, average });
+    }
+
+    if (results.length > 0) console.log(`\n${benchReport(results)}`);
+    return results;
+  }
+
+  /**
+   * Registers a function to be called once before all the tests.
+   *
+   * @param {Function} fn - The hook function.
+   */
+  beforeAll(fn) {
+    this.#registerHook('beforeAll', fn);
+  }
+
+  /**
+   * Registers a function to be called once after all the tests.
+   *
+   * @param {Function} fn - The hook function.
+   */
+  afterAll(fn) {
+    this.#registerHook('afterAll', fn);
+  }
+
+  /**
+   * Registers a function to be called before each test.
+   *
+   * @param {Function} fn - The hook function.
+   */
+  beforeEach(fn) {
+    this.#registerHook('beforeEach', fn);
+  }
+
+  /**
+   * Registers a function to be called after each test (even if it fails).
+   *
+   * @param {Function} fn - The hook function.
+   */
+  afterEach(fn) {
+    this.#registerHook('afterEach', fn);
+  }
+
+  /**
+   * Runs a single test function, applying its options (e.g. timeout).
+   *
+   * @param {Function} testFn - The test function to be executed.
+   * @param {Object} [context] - The context passed to the test (e.g. its `name` and `file`).
+   */
+  async runTest(testFn, context = {}) {
+    counter.assertions = 0;
+    context.output = [];
+    const resources = activeResources();
+    const ops = pendingOps();
+
+    setOpTracing(this.traceOps);
+    setResourceTracking(Boolean(testFn.sanitizeResources));
+    setOpTracking(Boolean(testFn.sanitizeOps));
+
+    // Cleanups can be deferred (or returned by the test) to run after the test.
+    const cleanups = [];
+    context.defer = (fn) => {
+      if (typeof fn !== 'function') {
+        throw new TypeError(`The "fn" argument must be of type function.`);
+      }
+      cleanups.push(fn);
+    };
+
+    // Note: Running the test in its own async context attributes any console
+    // output to it, even when it's written from timers or later promise jobs.
+    const errors = [];
+    await runWithContextValue(CURRENT_TEST, context, async () => {
+      try {
+        const result = await timeout(drive(testFn(context)), testFn.timeout);
+        if (typeof result === 'function') cleanups.push(result);
+      } catch (err) {
+        errors.push(err);
+      }
+
+      // Deferred cleanups run in reverse order, even if the test failed.
+      for (const cleanup of cleanups.reverse()) {
+        try {
+          await cleanup();
+        } catch (err) {
+          errors.push(err);
+        }
+      }
+    });
+
+    setResourceTracking(false);
+    setOpTracking(false);
+
+    if (errors.length === 1) throw errors[0];
+    if (errors.length > 1) {
+      const messages = errors.map((err) => `  - ${err?.message ?? err}`);
+      const summary = `The test failed with ${errors.length} errors:`;
+      throw new AggregateError(errors, [summary, ...messages].join('\n'));
+    }
+
+    // Check if the test left open any resources it opened.
+    if (testFn.sanitizeResources) {
+      checkLeakedResources(resources);
+    }
+
+    // Check if the test left pending any async ops (e.g. timers) it started.
+    if (testFn.sanitizeOps) {
+      checkLeakedOps(ops);
+    }
+
+    // Check if the test performed the expected number of assertions.
+    const expected = testFn.assertions;
+    if (expected !== undefined && counter.assertions !== expected) {
+      throw new Error(
+        `Expected ${expected} assertions to run, but ${counter.assertions} did.`
+      );
+    }
+  }
+
+  /**
+   * Runs a list of tests, executing independent tests concurrently when
+   * the runner's concurrency is greater than one.
+   *
+   * @param {Array} tests - A list of `[description, testFn]` entries.
+   */
+  async runTests(tests) {
+    let currentFile = null;
+    let index = 0;
+
+    while (index < tests.length) {
+      const [, testFn] = tests[index];
+
+      // Print a header when moving to the tests of another file.
+      if (testFn.file && testFn.file !== currentFile) {
+        const count = tests.filter(([, fn]) => fn.file === testFn.file).length;
+        const path = relativePath(testFn.file);
+        const header = `running ${count} tests from ${path}`;
+        console.log(currentFile ? `\n${header}` : header);
+      }
+      currentFile = testFn.file;
+
+      const batch = this.#nextBatch(tests, index);
+      const results =
+        batch.length > 1
+          ? await this.#executeBatch(batch)
+          : [await this.#executeTest(...batch[0])];
+      index += batch.length;
+
+      // Stop running the test suite (cancelling the remaining tests).
+      if (results.includes(false) && this.#shouldFailFast()) {
+        this.#cancelTests(tests.slice(index));
+        break;
+      }
+    }
+  }
+
+  // Runs a batch of tests concurrently, buffering the output (and report) of
+  // each test, so they're emitted in declaration order once all have finished.
+  async #executeBatch(batch) {
+    const start = this.results.length;
+    const buffers = batch.map(() => ({ owner: currentTest(), entries: [] }));
+
+    const results = await pool(
+      batch.map((_, i) => i),
+      this.concurrency,
+      (i) => runWithContextValue(OUTPUT_BUFFER, buffers[i], () =>
+        this.#executeTest(...batch[i])
+      )
+    );
+
+    for (const { entries } of buffers) {
+      entries.forEach((entry) => emitOutput(...entry));
+    }
+
+    // Note: Results are recorded as the tests complete, so they are sorted
+    // back into the order the tests were declared in.
+    const completed = this.results.splice(start);
+    this.results.push(
+      ...batch
+        .map(([name]) => completed.find((r) => r.description === name))
+        .filter(Boolean)
+    );
+
+    return results;
+  }
+
+  // Checks if enough tests failed to stop the test suite (`failFast` can be
+  // `true`, or the number of failures to stop at).
+  #shouldFailFast() {
+    if (!this.failFast) return false;
+    const limit = this.failFast === true ? 1 : Number(this.failFast);
+    return this.counters.failed >= limit;
+  }
+
+  #cancelTests(tests) {
+    for (const [description, testFn] of tests) {
+      this.counters.cancelled++;
+      this.results.push({
+        description,
+        file: testFn.file,
+        duration: 0,
+        slow: false,
+        ok: false,
+        cancelled: true,
+      });
+      console.log(`${CANCELLED} ${bright_black(description)}`);
+    }
+  }
+
+  // Returns the tests (starting from `index`) that can run concurrently.
+  #nextBatch(tests, index) {
+    const [, first] = tests[index];
+    if (!this.#canRunInParallel(first)) return [tests[index]];
+
+    let end = index + 1;
+    while (end < tests.length) {
+      const [, testFn] = tests[end];
+      if (testFn.file !== first.file || !this.#canRunInParallel(testFn)) break;
+      end++;
+    }
+
+    return tests.slice(index, end);
+  }
+
+  #canRunInParallel(testFn) {
+    // Note: Assertion counting and resource (or op) tracking rely on global
+    // state, so tests using them should never overlap with other tests.
+    return (
+      this.concurrency > 1 &&
+      testFn.parallel !== false &&
+      testFn.assertions === undefined &&
+      !testFn.sanitizeResources &&
+      !testFn.sanitizeOps
+    );
+  }
+
+  // Runs a test along with its hooks, returning `false` if it failed.
+  async #executeTest(description, testFn) {
+    // Check if the test should be ignored (reporting it as skipped).
+    const reason = ignoreReason(testFn);
+    if (reason !== null) {
+      this.counters.ignored++;
+      this.results.push({
+        description,
+        file: testFn.file,
+        duration: 0,
+        slow: false,
+        ok: false,
+        ignored: true,
+        ...(reason && { reason }),
+      });
+      const why = reason ? ` ${bright_black(`(${reason})`)}` : '';
+      console.log(`${IGNORED} ${bright_black(description)}${why}`);
+      return;
+    }
+
+    let failure = null;
+
+    try {
+      await this.#runHooks('beforeEach');
+    } catch (err) {
+      failure = err;
+      this.#reportHookFailure('beforeEach', err, description);
+    }
+
+    let duration = 0;
+    let location = null;
+
+    // Skip the test if a `beforeEach` hook has failed.
+    if (!failure) {
+      const startTime = performance.now();
+      try {
+        const context = { name: description, file: testFn.file };
+        await this.runTest(testFn, context);
+      } catch (err) {
+        failure = err;
+      }
+      duration = performance.now() - startTime;
+
+      if (failure) {
+        // Point to the failing assertion, so it can be found quickly.
+        location = failureLocation(failure, testFn.file);
+
+        const timing = this.#formatDuration(duration);
+        const where = location ? ` ${bright_black(location)}` : '';
+        const stack = red(failure.stack);
+        console.log(`${FAIL} ${red(description)} ${timing}${where}\n ${stack}`);
+      }
+    }
+
+    // The `afterEach` hooks should always run (for cleanup).
+    try {
+      await this.#runHooks('afterEach');
+    } catch (err) {
+      if (!failure) this.#reportHookFailure('afterEach', err, description);
+      failure = failure || err;
+    }
+
+    const slow = duration >= this.slowThreshold;
+    const file = testFn.file;
+    this.results.push({
+      description,
+      file,
+      duration,
+      slow,
+      ok: !failure,
+      ...(failure && { error: failure }),
+      ...(location && { location }),
+    });
+
+    if (!failure) {
+      this.counters.ok++;
+      const timing = this.#formatDuration(duration);
+      console.log(`${OK} ${green(description)} ${timing}`);
+      return true;
+    }
+
+    this.counters.failed++;
+    return false;
+  }
+
+  // Formats a test's duration, marking tests exceeding the slow threshold.
+  #formatDuration(duration) {
+    const timing = bright_black(`(${Math.trunc(duration)} ms)`);
+    const slow = duration >= this.slowThreshold;
+    return slow ? `${timing} ${yellow('(slow)')}` : timing;
+  }
+
+  #registerHook(type, fn) {
+    if (typeof fn !== 'function') {
+      throw new TypeError(`The "fn" argument must be of type function.`);
+    }
+    this.hooks[type].push(fn);
+  }
+
+  async #runHooks(type) {
+    for (const hook of this.hooks[type]) {
+      await hook();
+    }
+  }
+
+  // Reports a hook failure, distinctly from a regular test failure.
+  #reportHookFailure(type, err, description) {
+    const target = description ? ` for "${description}"` : '';
+    const label = red(`"${type}" hook${target}`);
+    console.log(`${FAIL} ${label}\n ${red(err.stack)}`);
+  }
+
   #walkDirs(path, files = []) {
     // Read all files/folders from current path.
     const entities = fs.readdirSync(path);
@@ -124,62 +892,165 @@ export class TestRunner {
       this.testFiles.push(entryPoint);
     }
 
-    await Promise.all(this.testFiles.map((filename) => import(filename)));
+    // Note: Besides the `test(...)` calls, a module may default-export an
+    // array of `{ name, fn }` descriptors. Any other export is ignored.
+    const previousRunner = importingRunner;
+    importingRunner = this;
+    try {
+      const modules = await Promise.all(
+        this.testFiles.map((filename) => import(filename))
+      );
+      modules.forEach((module, i) => {
+        this.#registerDescriptors(module.default, this.testFiles[i]);
+      });
+    } finally {
+      // Note: Nested imports go back to registering into the outer runner.
+      importingRunner = previousRunner;
+    }
   }
 
   /**
-   * Runs all the registered tests as a test suite.
+   * Registers the test descriptors exported by a test module.
+   *
+   * @param {*} descriptors - The module's default export.
+   * @param {String} filename - The module the descriptors come from.
    */
-  async run() {
+  #registerDescriptors(descriptors, filename) {
+    const isDescriptor = (value) =>
+      typeof value?.name === 'string' && typeof value?.fn === 'function';
+
+    if (!Array.isArray(descriptors) || !descriptors.every(isDescriptor)) {
+      return;
+    }
+
+    for (const { name, fn, ...options } of descriptors) {
+      Object.assign(fn, { ignore: false, timeout: 10000, ...options });
+      fn.file = filename;
+      this.test(name, fn);
+    }
+  }
+
+  /**
+   * Returns the tests that will run (in order) after applying the filters.
+   *
+   * @returns {Array} A list of `[description, testFn]` entries grouped by file.
+   */
+  collect() {
+    // Note: The programmatic filter takes precedence over the environment.
+    const filter = this.filter ?? envFilter();
+
+    let tests = [...this.tests].filter(([description, testFn]) => {
+      // Filter tests based on provided regex.
+      if (filter && !filter.test(description)) return false;
+
+      // Filter tests based on the file they were registered from.
+      const { file } = testFn;
+      if (this.fileFilter && !file?.startsWith(this.fileFilter)) return false;
+
+      return true;
+    });
+
+    // Shuffle the tests if requested.
+    if (this.shuffle !== undefined && this.shuffle !== false) {
+      tests = shuffle(tests, Number(this.shuffle));
+    }
+
+    // Group the tests by the file they were registered from.
+    const files = [...new Set(tests.map(([, testFn]) => testFn.file))];
+    return files.flatMap((file) => tests.filter(([, fn]) => fn.file === file));
+  }
+
+  /**
+   * Runs all the registered tests as a test suite, printing a summary line.
+   *
+   * @returns {Promise<Object>} The `passed`, `failed`, `ignored`, `filtered` and `cancelled` counts, along with the `duration` (in ms), the `results` and the suite's `exitCode`.
+   */
+  async runSuite() {
     // Start test suite clock.
     const startTime = performance.now();
 
-    // Run test suite.
-    for await (const [description, testFn] of this.tests) {
-      // Filter tests based on provided regex.
-      if (this.filter && !this.filter.test(description)) {
-        continue;
-      }
+    // Shuffle the tests if requested (`true` means a random seed).
+    if (this.shuffle === true) {
+      this.shuffle = Math.floor(Math.random() * 2 ** 32);
+    }
 
-      // Check if the test should be ignored.
-      if (testFn.ignore) {
-        this.counters.ignored++;
-        continue;
-      }
+    if (this.shuffle !== undefined && this.shuffle !== false) {
+      console.log(`Running tests in random order (seed: ${this.shuffle})\n`);
+    }
 
-      try {
-        await timeout(testFn(), testFn.timeout);
-        this.counters.ok++;
-        console.log(`${OK} ${green(description)}`);
-      } catch (err) {
-        this.counters.failed++;
-        console.log(`${FAIL} ${red(description)}\n ${red(err.stack)}`);
-
-        // Stop running test suite.
-        if (this.failFast) {
-          const { ok, ignored } = this.counters;
-          const remaining = this.tests.size - ok - ignored - 1;
-          this.counters.ignored += remaining;
-          break;
-        }
-      }
+    const tests = this.collect();
+    this.counters.filtered = this.tests.size - tests.length;
+
+    // Run the `beforeAll` hooks, if they fail no test will be executed.
+    let canRunTests = true;
+    try {
+      await this.#runHooks('beforeAll');
+    } catch (err) {
+      canRunTests = false;
+      this.counters.failed++;
+      this.counters.ignored += tests.length;
+      this.#reportHookFailure('beforeAll', err);
+    }
+
+    // Run test suite (and the benchmarks).
+    if (canRunTests) await this.runTests(tests);
+    if (canRunTests) await this.runBenchmarks();
+
+    // The `afterAll` hooks should always run (for cleanup).
+    try {
+      await this.#runHooks('afterAll');
+    } catch (err) {
+      this.counters.failed++;
+      this.#reportHookFailure('afterAll', err);
     }
 
-    const { ok, failed, ignored } = this.counters;
+    const { ok, failed, ignored, filtered, cancelled } = this.counters;
+    const duration = Math.trunc(performance.now() - startTime);
 
-    // Create output strings.
-    const elapsedTime = Math.trunc(performance.now() - startTime);
-    const result = `${ok} ok; ${failed} failed; ${ignored} ignored`;
+    // Create the summary line (e.g. `ok | 2 passed | 0 failed | 0 ignored`).
+    const status = failed > 0 ? red('FAILED') : green('ok');
+    let summary = `${status} | ${ok} passed | ${failed} failed`;
+    summary += ` | ${ignored} ignored`;
+    if (filtered > 0) summary += ` | ${filtered} filtered`;
+    if (cancelled > 0) summary += ` | ${cancelled} cancelled`;
 
-    console.log(`\nTest result: ${result} (${elapsedTime} ms)`);
+    console.log(`\n${summary} ${bright_black(`(${duration}ms)`)}`);
 
-    // Exit with non-zero code if we have test failure.
-    process.exit(failed > 0 ? 1 : 0);
+    // Write the JUnit report (for CI dashboards), if requested.
+    if (this.junit) fs.writeFileSync(this.junit, toJUnit(this.results));
+
+    return {
+      passed: ok,
+      failed,
+      ignored,
+      filtered,
+      cancelled,
+      duration,
+      results: this.results,
+      exitCode: failed > 0 ? 1 : 0,
+    };
+  }
+
+  /**
+   * Runs all the registered tests as a test suite, and exits the process
+   * (with a non-zero code if any test failed).
+   */
+  async run() {
+    const { exitCode } = await this.runSuite();
+    process.exit(exitCode);
   }
 }
 
 export const mainRunner = new TestRunner();
 
+// The runner currently importing test modules (tests registered through the
+// top-level functions are added to it, instead of the main runner).
+let importingRunner = null;
+
+function currentRunner() {
+  return importingRunner || mainRunner;
+}
+
 function parseOptionsArgs(args) {
   // Check if enough arguments are specified.
   if (args.length < 2) {
@@ -200,7 +1071,12 @@ function parseOptionsArgs(args) {
  * @param {string} description - A brief description of the test.
  * @param {string} testFn - The test function where the actual test logic is implemented.
  * @param {Object} [options] - Additional configuration options for the test.
- * @param {boolean} [options.ignore] - The test will be registered but not executed.
+ * @param {(boolean|string|string[]|Function)} [options.ignore] - The test will be registered but not executed (always, on the given platforms, or when the function returns `true`).
+ * @param {string} [options.reason] - Why the test is ignored (shown in the report).
+ * @param {number} [options.assertions] - The number of assertions the test is expected to run.
+ * @param {boolean} [options.parallel] - Set to `false` to never run the test concurrently with others.
+ * @param {boolean} [options.sanitizeResources] - Fail the test if it leaves open resources (e.g. files) it opened.
+ * @param {boolean} [options.sanitizeOps] - Fail the test if it leaves pending async ops (e.g. timers) it started.
  */
 function test(...params) {
   // Parse variadic parameters.
@@ -217,7 +1093,139 @@ function test(...params) {
   // Hack: attach options to the test function.
   Object.assign(testFn, options);
 
-  mainRunner.test(description, testFn);
+  currentRunner().test(description, testFn);
+}
+
+/**
+ * Registers a test that will be reported as ignored, without running it.
+ *
+ * @param {string} description - A brief description of the test.
+ * @param {Object} [options] - Additional configuration options for the test (see `test`).
+ * @param {Function} testFn - The test function.
+ */
+test.ignore = function ignore(...params) {
+  const [description, testFn, options] = parseOptionsArgs(params);
+  test(description, { ...options, ignore: true }, testFn);
+};
+
+// Formats the name of a parameterized test, substituting the values of the
+// case into the template (`%s`, `%i`, `%d`, `%j`, `%o`, `%#` and `$0`, `$1`...).
+function formatCaseName(template, testCase, index) {
+  const values = Array.isArray(testCase) ? [...testCase] : [testCase];
+  const formatters = {
+    '%': () => '%',
+    '#': () => String(index),
+    s: (value) => String(value),
+    i: (value) => String(Math.trunc(Number(value))),
+    d: (value) => String(Number(value)),
+    j: (value) => JSON.stringify(value),
+    o: (value) => inspect(value, { colors: false }),
+  };
+
+  let position = 0;
+  const name = template
+    .replace(/%([%#sidjo])/g, (_, type) =>
+      type === '%' || type === '#'
+        ? formatters[type]()
+        : formatters[type](values[position++])
+    )
+    .replace(/\$(\d+)/g, (match, n) =>
+      n < values.length ? formatters.s(values[n]) : match
+    );
+
+  // Note: Templates without placeholders still produce unique names.
+  return name === template ? `${template} (case ${index})` : name;
+}
+
+/**
+ * Registers one test per case, substituting the case values into the name
+ * template (e.g. `test.each([[1, 2]])('adds %i and %i', ([a, b]) => {})`).
+ *
+ * @param {Array} cases - The cases (arrays of values, or any other value).
+ * @returns {Function} A function taking the name template, the test function (called with the case and the context) and the test's options.
+ */
+test.each = function each(cases) {
+  if (!Array.isArray(cases)) {
+    throw new TypeError(`The "cases" argument must be of type array.`);
+  }
+
+  return (...params) => {
+    const [template, testFn, options] = parseOptionsArgs(params);
+
+    if (typeof template !== 'string') {
+      throw new TypeError(`The "template" argument must be of type string.`);
+    }
+
+    if (typeof testFn !== 'function') {
+      throw new TypeError(`The "testFn" argument must be of type function.`);
+    }
+
+    cases.forEach((testCase, index) => {
+      const name = formatCaseName(template, testCase, index);
+      test(name, (context) => testFn(testCase, context), options);
+    });
+  };
+};
+
+/**
+ * Specifies a benchmark to be registered with the default test runner.
+ *
+ * @param {string} name - The name of the benchmark.
+ * @param {Object} [options] - Additional configuration options for the benchmark.
+ * @param {string} [options.group] - Benchmarks of the same group are compared with each other.
+ * @param {boolean} [options.baseline] - The benchmark the rest of its group is compared against.
+ * @param {number} [options.iterations] - How many times the function is measured (default: 100).
+ * @param {Function} benchFn - The function to measure.
+ */
+export function bench(...params) {
+  const [name, benchFn, options] = parseOptionsArgs(params);
+
+  if (typeof name !== 'string') {
+    throw new TypeError(`The "name" argument must be of type string.`);
+  }
+
+  if (typeof benchFn !== 'function') {
+    throw new TypeError(`The "benchFn" argument must be of type function.`);
+  }
+
+  Object.assign(benchFn, options);
+  currentRunner().bench(name, benchFn);
+}
+
+/**
+ * Registers a function to be called once before all the tests.
+ *
+ * @param {Function} fn - The hook function.
+ */
+export function beforeAll(fn) {
+  currentRunner().beforeAll(fn);
+}
+
+/**
+ * Registers a function to be called once after all the tests.
+ *
+ * @param {Function} fn - The hook function.
+ */
+export function afterAll(fn) {
+  currentRunner().afterAll(fn);
+}
+
+/**
+ * Registers a function to be called before each test.
+ *
+ * @param {Function} fn - The hook function.
+ */
+export function beforeEach(fn) {
+  currentRunner().beforeEach(fn);
+}
+
+/**
+ * Registers a function to be called after each test (even if it fails).
+ *
+ * @param {Function} fn - The hook function.
+ */
+export function afterEach(fn) {
+  currentRunner().afterEach(fn);
 }
 
 export default test;
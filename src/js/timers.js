@@ -8,7 +8,8 @@
  * @module Timers
  */
 
-import assert from 'assert';
+import { Assert as assert } from 'assert';
+import { bindAsyncContext, trackOp, untrackOp } from 'util';
 
 const TIMEOUT_MAX = Math.pow(2, 31) - 1;
 
@@ -39,9 +40,9 @@ export function setTimeout(callback, delay, ...args) {
   // Coalesce to number or NaN.
   delay *= 1;
 
-  // Check delay's boundaries.
-  if (!(delay >= 1 && delay <= TIMEOUT_MAX)) {
-    delay = 1;
+  // Check delay's boundaries (out of range delays fire immediately).
+  if (!(delay >= 0 && delay <= TIMEOUT_MAX)) {
+    delay = 0;
   }
 
   // Check if callback is a valid function.
@@ -49,11 +50,13 @@ export function setTimeout(callback, delay, ...args) {
 
   // Pin down the correct ID value.
   const id = nextId++;
+  const task = bindAsyncContext(callback);
 
   const timer = binding.createTimeout(
     () => {
-      callback(...args);
+      task(...args);
       activeTimers.delete(id);
+      untrackOp(id);
     },
     delay,
     false
@@ -61,6 +64,7 @@ export function setTimeout(callback, delay, ...args) {
 
   // Update `activeTimers` map.
   activeTimers.set(id, timer);
+  trackOp(id, `A timer (setTimeout) with a ${delay}ms delay`);
 
   return id;
 }
@@ -78,6 +82,7 @@ export function clearTimeout(id) {
   if (activeTimers.has(id)) {
     binding.removeTimeout(activeTimers.get(id));
     activeTimers.delete(id);
+    untrackOp(id);
   }
 }
 
@@ -104,10 +109,12 @@ export function setInterval(callback, delay, ...args) {
 
   // Pin down the correct ID value.
   const id = nextId++;
-  const timer = binding.createTimeout(callback, delay, true, args);
+  const task = bindAsyncContext(callback);
+  const timer = binding.createTimeout(task, delay, true, args);
 
   // Update `activeTimers` map.
   activeTimers.set(id, timer);
+  trackOp(id, `An interval (setInterval) with a ${delay}ms delay`);
 
   return id;
 }
@@ -135,13 +142,16 @@ export function setImmediate(callback, ...args) {
 
   // Pin down the correct ID value.
   const id = nextId++;
+  const task = bindAsyncContext(callback);
   const immediate = binding.createImmediate(() => {
-    callback(...args);
+    task(...args);
     activeTimers.delete(id);
+    untrackOp(id);
   });
 
   // Update `activeTimers` map.
   activeTimers.set(id, immediate);
+  trackOp(id, 'An immediate (setImmediate)');
 
   return id;
 }
@@ -158,6 +168,7 @@ export function clearImmediate(id) {
   if (activeTimers.has(id)) {
     binding.removeImmediate(activeTimers.get(id));
     activeTimers.delete(id);
+    untrackOp(id);
   }
 }
 
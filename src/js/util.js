@@ -12,3 +12,284 @@ export function cloneFunction(fn) {
   }
   return temp;
 }
+
+// Note: The async context is an (immutable) map of values that V8 preserves
+// across promise continuations. Timers and ticks capture it explicitly.
+
+function getAsyncContext() {
+  return process.binding('util').getAsyncContext();
+}
+
+function setAsyncContext(context) {
+  process.binding('util').setAsyncContext(context);
+}
+
+/**
+ * Returns the value stored under `key` in the current async context.
+ *
+ * @param {*} key - The key the value was stored under.
+ * @returns {*}
+ */
+export function getContextValue(key) {
+  return getAsyncContext()?.get(key);
+}
+
+/**
+ * Runs a function with `key` set to `value` in its async context. Any async
+ * work scheduled by the function keeps seeing the same value.
+ *
+ * @param {*} key - The key to store the value under.
+ * @param {*} value - The value to store.
+ * @param {Function} fn - The function to run.
+ * @param {...*} [args] - Arguments passed to the function.
+ * @returns {*} The function's return value.
+ */
+export function runWithContextValue(key, value, fn, ...args) {
+  const context = new Map(getAsyncContext());
+  context.set(key, value);
+  return runInContext(context, fn, args);
+}
+
+/**
+ * Binds a function to the current async context, so it runs under the same
+ * context no matter where it's called from.
+ *
+ * @param {Function} fn - The function to bind.
+ * @returns {Function}
+ */
+export function bindAsyncContext(fn) {
+  const context = getAsyncContext();
+  return (...args) => runInContext(context, fn, args);
+}
+
+function runInContext(context, fn, args) {
+  const previous = getAsyncContext();
+  setAsyncContext(context);
+  try {
+    return fn(...args);
+  } finally {
+    setAsyncContext(previous);
+  }
+}
+
+// Holds the open resources (e.g. file handles), along with their description
+// and the stack trace of the place they were opened at.
+//
+// Note: Resources are only tracked while a test asks for it, and are held
+// weakly, so the ones that are never closed can still be garbage-collected.
+const resources = new Map();
+const resourceRefs = new WeakMap();
+const collected = new FinalizationRegistry((ref) => resources.delete(ref));
+
+let trackResources = false;
+
+/**
+ * Enables tracking the resources that are opened (e.g. for leak checks).
+ *
+ * @param {Boolean} enabled - Whether resource tracking should be enabled.
+ */
+export function setResourceTracking(enabled) {
+  trackResources = enabled;
+}
+
+/**
+ * Registers an open resource (used to report leaks in tests).
+ *
+ * @param {Object} resource - The resource object.
+ * @param {String} description - A human-readable description of the resource.
+ */
+export function trackResource(resource, description) {
+  if (!trackResources) return;
+
+  const stack = new Error().stack.split('\n').slice(2).join('\n');
+  const ref = new WeakRef(resource);
+  resourceRefs.set(resource, ref);
+  resources.set(ref, { description, stack });
+  collected.register(resource, ref, ref);
+}
+
+/**
+ * Unregisters a resource that has been closed.
+ *
+ * @param {Object} resource - The resource object.
+ */
+export function untrackResource(resource) {
+  const ref = resourceRefs.get(resource);
+  if (!ref) return;
+
+  resourceRefs.delete(resource);
+  resources.delete(ref);
+  collected.unregister(ref);
+}
+
+/**
+ * Returns the currently open resources.
+ *
+ * @returns {Map<Object, Object>}
+ */
+export function activeResources() {
+  const active = new Map();
+  for (const [ref, entry] of resources) {
+    const resource = ref.deref();
+    if (resource) active.set(resource, entry);
+  }
+  return active;
+}
+
+// Holds the pending async operations (e.g. timers), along with their
+// description and (when op tracing is enabled) the place they were started at.
+//
+// Note: Ops are only recorded while a test sanitizes them (or op tracing is
+// enabled), so they cost nothing the rest of the time.
+const ops = new Map();
+
+let trackOps = false;
+let traceOps = false;
+
+/**
+ * Enables tracking the async operations that are started (e.g. for leaks).
+ *
+ * @param {Boolean} enabled - Whether op tracking should be enabled.
+ */
+export function setOpTracking(enabled) {
+  trackOps = enabled;
+}
+
+/**
+ * Enables capturing a stack trace for every async operation that's started.
+ *
+ * @param {Boolean} enabled - Whether op tracing should be enabled.
+ */
+export function setOpTracing(enabled) {
+  traceOps = enabled;
+}
+
+function recordOp(op, description, depth) {
+  // Note: Capturing stack traces is expensive, so it's opt-in.
+  const stack = traceOps
+    ? new Error().stack.split('\n').slice(depth).join('\n')
+    : null;
+  ops.set(op, { description, stack });
+}
+
+/**
+ * Registers a pending async operation (used to report leaks in tests).
+ *
+ * @param {*} op - A value identifying the operation.
+ * @param {String} description - A human-readable description of the operation.
+ */
+export function trackOp(op, description) {
+  if (trackOps || traceOps) recordOp(op, description, 3);
+}
+
+/**
+ * Unregisters an async operation that has completed (or was cancelled).
+ *
+ * @param {*} op - A value identifying the operation.
+ */
+export function untrackOp(op) {
+  ops.delete(op);
+}
+
+/**
+ * Wraps the functions of a native binding, so the async operations they start
+ * are tracked until their promises settle.
+ *
+ * @param {Object} binding - The native binding (e.g. `process.binding('fs')`).
+ * @param {String} name - The name the operations are described with.
+ * @returns {Object} The wrapped binding.
+ */
+export function trackBindingOps(binding, name) {
+  const tracked = { ...binding };
+
+  for (const [key, fn] of Object.entries(binding)) {
+    if (typeof fn !== 'function') continue;
+
+    tracked[key] = (...args) => {
+      const result = fn.apply(binding, args);
+      if (!(result instanceof Promise) || (!trackOps && !traceOps)) {
+        return result;
+      }
+
+      const target = typeof args[0] === 'string' ? ` on "${args[0]}"` : '';
+      const op = {};
+      recordOp(op, `An async op (${name}.${key})${target}`, 3);
+      return result.finally(() => untrackOp(op));
+    };
+  }
+
+  return tracked;
+}
+
+/**
+ * Returns the currently pending async operations.
+ *
+ * @returns {Map<*, Object>}
+ */
+export function pendingOps() {
+  return new Map(ops);
+}
+
+const BASE64 =
+  'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/';
+
+// Decodes the base64 VLQ mappings of a source map into lines of segments,
+// where each segment is `[generatedColumn, sourceLine, sourceColumn]`.
+function decodeMappings(mappings) {
+  const fields = [0, 0, 0, 0, 0];
+
+  return mappings.split(';').map((line) => {
+    fields[0] = 0;
+
+    return line
+      .split(',')
+      .filter(Boolean)
+      .map((segment) => {
+        let value = 0;
+        let shift = 0;
+        let index = 0;
+
+        for (const char of segment) {
+          const digit = BASE64.indexOf(char);
+          value += (digit & 31) << shift;
+          if (digit & 32) {
+            shift += 5;
+            continue;
+          }
+          // Note: Every field is relative to its previous occurrence.
+          fields[index++] += value & 1 ? -(value >>> 1) : value >>> 1;
+          value = shift = 0;
+        }
+
+        // Note: Segments without a source position are not mapped.
+        return index >= 4 ? [fields[0], fields[2], fields[3]] : null;
+      })
+      .filter(Boolean);
+  });
+}
+
+const mappingsCache = new Map();
+
+/**
+ * Maps a (1-based) position of a loaded module to its original source, using
+ * the module's source map (e.g. for transpiled TypeScript).
+ *
+ * @param {String} path - The module's path (as it appears in stack traces).
+ * @param {Number} line - The generated line.
+ * @param {Number} column - The generated column.
+ * @returns {{ line: Number, column: Number }}
+ */
+export function originalPosition(path, line, column) {
+  if (!mappingsCache.has(path)) {
+    const sourceMap = process.binding('util').getSourceMap(path);
+    const mappings = sourceMap && JSON.parse(sourceMap).mappings;
+    mappingsCache.set(path, mappings ? decodeMappings(mappings) : null);
+  }
+
+  const segments = mappingsCache.get(path)?.[line - 1] ?? [];
+  const segment = segments.findLast(([generated]) => generated < column);
+
+  return segment
+    ? { line: segment[1] + 1, column: segment[2] + 1 }
+    : { line, column };
+}
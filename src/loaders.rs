@@ -16,6 +16,7 @@ use sha::utils::Digest;
 use sha::utils::DigestExt;
 use std::env;
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
 use url::Url;
@@ -28,8 +29,51 @@ pub trait ModuleLoader {
 
 static EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "json", "wasm"];
 
+/// The size limit (in bytes) of downloaded modules when none is configured.
+const DEFAULT_MAX_DOWNLOAD_SIZE: u64 = 10 * 1024 * 1024;
+
+/// The error returned when a module's source exceeds the maximum size.
+#[derive(Debug)]
+pub struct ModuleSizeError {
+    name: String,
+    max_size: u64,
+}
+
+impl std::error::Error for ModuleSizeError {}
+
+impl std::fmt::Display for ModuleSizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (name, max_size) = (&self.name, self.max_size);
+        write!(
+            f,
+            "Module \"{name}\" exceeds the maximum module size ({max_size} bytes)"
+        )
+    }
+}
+
+/// Reads a module's source, failing (without reading any further) when it's
+/// larger than `max_size` bytes.
+fn read_source(reader: impl Read, name: &str, max_size: Option<u64>) -> Result<ModuleSource> {
+    let limit = max_size.map_or(u64::MAX, |max_size| max_size.saturating_add(1));
+    let mut bytes = vec![];
+
+    reader.take(limit).read_to_end(&mut bytes)?;
+
+    if let Some(max_size) = max_size {
+        if bytes.len() as u64 > max_size {
+            let name = name.to_string();
+            return Err(ModuleSizeError { name, max_size }.into());
+        }
+    }
+
+    Ok(String::from_utf8(bytes)?)
+}
+
 #[derive(Default)]
-pub struct FsModuleLoader;
+pub struct FsModuleLoader {
+    // The maximum size (in bytes) of a module's source.
+    pub max_size: Option<u64>,
+}
 
 impl FsModuleLoader {
     /// Transforms PathBuf into String.
@@ -52,7 +96,8 @@ impl FsModuleLoader {
 
     /// Loads contents from a file.
     fn load_source(&self, path: &Path) -> Result<ModuleSource> {
-        let source = fs::read_to_string(path)?;
+        let file = fs::File::open(path)?;
+        let source = read_source(file, &path.display().to_string(), self.max_size)?;
         let source = match self.is_json_import(path) {
             true => self.wrap_json(source.as_str()),
             false => source,
@@ -122,7 +167,10 @@ impl ModuleLoader for FsModuleLoader {
         let path = Path::new(specifier);
         let maybe_source = self
             .load_as_file(path)
-            .or_else(|_| self.load_as_directory(path));
+            .or_else(|e| match e.is::<ModuleSizeError>() {
+                true => Err(e),
+                false => self.load_as_directory(path),
+            });
 
         // Append default extension (if none specified).
         let path = match path.extension() {
@@ -132,6 +180,7 @@ impl ModuleLoader for FsModuleLoader {
 
         let source = match maybe_source {
             Ok(source) => source,
+            Err(e) if e.is::<ModuleSizeError>() => return Err(e),
             Err(_) => bail!(format!("Module not found \"{}\"", path.display())),
         };
 
@@ -165,6 +214,8 @@ lazy_static! {
 pub struct UrlModuleLoader {
     // Ignores the cache and re-downloads the dependency.
     pub skip_cache: bool,
+    // The maximum size (in bytes) of a module's source.
+    pub max_size: Option<u64>,
 }
 
 impl ModuleLoader for UrlModuleLoader {
@@ -202,16 +253,18 @@ impl ModuleLoader for UrlModuleLoader {
         if !self.skip_cache {
             // Check cache, and load file.
             if module_path.is_file() {
-                let source = fs::read_to_string(&module_path).unwrap();
-                return Ok(source);
+                return read_source(fs::File::open(&module_path)?, specifier, self.max_size);
             }
         }
 
         println!("{} {}", "Downloading".green(), specifier);
 
-        // Download file and, save it to cache.
-        let source = match ureq::get(specifier).call()?.into_string() {
+        // Download file (streaming it, to respect the size limit) and, save it to cache.
+        let max_size = self.max_size.unwrap_or(DEFAULT_MAX_DOWNLOAD_SIZE);
+        let reader = ureq::get(specifier).call()?.into_reader();
+        let source = match read_source(reader, specifier, Some(max_size)) {
             Ok(source) => source,
+            Err(e) if e.is::<ModuleSizeError>() => return Err(e),
             Err(_) => bail!(format!("Module not found \"{specifier}\"")),
         };
 
@@ -285,7 +338,7 @@ mod tests {
         ];
 
         // Run tests.
-        let loader = FsModuleLoader;
+        let loader = FsModuleLoader::default();
 
         for (base, specifier, expected) in tests {
             let path = loader.resolve(base, specifier).unwrap();
@@ -332,7 +385,7 @@ mod tests {
         ];
 
         // Run tests.
-        let loader = FsModuleLoader;
+        let loader = FsModuleLoader::default();
 
         for specifier in tests {
             let path = format!("{}", temp_dir.child(specifier).display());
@@ -343,6 +396,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_load_fs_imports_with_size_limit() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let file = temp_dir.child("oversized.js");
+
+        file.write_str(&"// padding\n".repeat(100)).unwrap();
+
+        let path = format!("{}", file.display());
+        let loader = FsModuleLoader {
+            max_size: Some(256),
+        };
+
+        let error = loader.load(&path).unwrap_err();
+
+        assert!(error.is::<ModuleSizeError>());
+        let message = error.to_string();
+
+        assert!(message.contains("exceeds the maximum module size (256 bytes)"));
+
+        // Modules within the limit are loaded as usual.
+        let loader = FsModuleLoader {
+            max_size: Some(2048),
+        };
+
+        assert!(loader.load(&path).is_ok());
+    }
+
     #[test]
     fn test_resolve_url_imports() {
         // Group of tests to be run.
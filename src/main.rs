@@ -1,5 +1,6 @@
 mod bindings;
 mod cli;
+mod coverage;
 mod dns;
 mod dotenv;
 mod errors;
@@ -21,6 +22,7 @@ mod stdio;
 mod timers;
 mod tools;
 mod transpilers;
+mod util;
 mod watcher;
 
 use crate::cli::process_cli_arguments;
@@ -8,6 +8,7 @@ use crate::loaders::UrlModuleLoader;
 use crate::runtime::JsFuture;
 use crate::runtime::JsRuntime;
 use anyhow::anyhow;
+use anyhow::bail;
 use anyhow::Error;
 use anyhow::Result;
 use dune_event_loop::LoopHandle;
@@ -17,6 +18,7 @@ use regex::Regex;
 use serde_json::Value;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::LinkedList;
 use std::env;
 use std::fs;
@@ -35,12 +37,14 @@ lazy_static! {
             ("util", include_str!("./js/util.js")),
             ("fs", include_str!("./js/fs.js")),
             ("perf_hooks", include_str!("./js/perf-hooks.js")),
+            ("async_hooks", include_str!("./js/async-hooks.js")),
             ("colors", include_str!("./js/colors.js")),
             ("dns", include_str!("./js/dns.js")),
             ("net", include_str!("./js/net.js")),
             ("test", include_str!("./js/test.js")),
             ("stream", include_str!("./js/stream.js")),
             ("http", include_str!("./js/http.js")),
+            ("@web/event_target", include_str!("./js/event-target.js")),
             ("@web/abort", include_str!("./js/abort-controller.js")),
             ("@web/text_encoding", include_str!("./js/text-encoding.js")),
             ("@web/clone", include_str!("./js/structured-clone.js")),
@@ -82,16 +86,33 @@ pub struct ModuleMap {
     pub index: HashMap<ModulePath, v8::Global<v8::Module>>,
     pub seen: HashMap<ModulePath, ModuleStatus>,
     pub pending: Vec<Rc<RefCell<ModuleGraph>>>,
+    // The maximum number of (user) modules to keep cached, if bounded.
+    pub limit: Option<usize>,
+    // The modules statically imported by each module.
+    imports: HashMap<ModulePath, HashSet<ModulePath>>,
+    // How many cached modules statically import each module.
+    importers: HashMap<ModulePath, usize>,
+    // The last time each module was used (for the LRU eviction).
+    last_used: HashMap<ModulePath, u64>,
+    clock: u64,
+    // The paths of evicted modules that may still be alive.
+    evicted: Vec<(v8::Weak<v8::Module>, ModulePath)>,
 }
 
 impl ModuleMap {
-    // Creates a new module-map instance.
-    pub fn new() -> ModuleMap {
+    // Creates a new module-map instance (caching at most `limit` modules).
+    pub fn new(limit: Option<usize>) -> ModuleMap {
         Self {
             main: None,
             index: HashMap::new(),
             seen: HashMap::new(),
             pending: vec![],
+            limit,
+            imports: HashMap::new(),
+            importers: HashMap::new(),
+            last_used: HashMap::new(),
+            clock: 0,
+            evicted: vec![],
         }
     }
 
@@ -102,6 +123,21 @@ impl ModuleMap {
             self.main = Some(path.into());
         }
         self.index.insert(path.into(), module);
+        self.touch(path);
+    }
+
+    // Marks a module as recently used.
+    pub fn touch(&mut self, path: &str) {
+        self.clock += 1;
+        self.last_used.insert(path.into(), self.clock);
+    }
+
+    // Records that a module statically imports another one.
+    pub fn add_import(&mut self, importer: &str, specifier: &str) {
+        let imports = self.imports.entry(importer.into()).or_default();
+        if imports.insert(specifier.into()) && importer != specifier {
+            *self.importers.entry(specifier.into()).or_default() += 1;
+        }
     }
 
     // Returns if there are still pending imports to be loaded.
@@ -122,10 +158,91 @@ impl ModuleMap {
             .map(|(p, _)| p.clone())
     }
 
+    // Returns the specifier of a module that has been evicted from the map.
+    pub fn get_evicted_path(
+        &self,
+        scope: &mut v8::HandleScope,
+        module: v8::Local<v8::Module>,
+    ) -> Option<ModulePath> {
+        self.evicted
+            .iter()
+            .find(|(evicted, _)| evicted.to_local(scope) == Some(module))
+            .map(|(_, path)| path.clone())
+    }
+
     // Returns the main entry point.
     pub fn main(&self) -> Option<ModulePath> {
         self.main.clone()
     }
+
+    // Returns the number of cached modules counting towards the limit.
+    pub fn cached_modules(&self) -> usize {
+        self.index
+            .keys()
+            .filter(|path| !is_core_module(path))
+            .count()
+    }
+
+    // Checks if a module can be safely removed from the map.
+    fn is_evictable(&self, path: &str) -> bool {
+        // Note: Modules imported by other cached modules are still needed to
+        // instantiate them, and modules in flight may not be evaluated yet.
+        !is_core_module(path)
+            && self.main.as_deref() != Some(path)
+            && self.seen.get(path) == Some(&ModuleStatus::Ready)
+            && self.importers.get(path).copied().unwrap_or_default() == 0
+    }
+
+    // Evicts the least recently used modules while the cache exceeds its limit.
+    pub fn evict(&mut self, isolate: &mut v8::Isolate) {
+        let limit = match self.limit {
+            Some(limit) if !self.has_pending_imports() => limit,
+            _ => return,
+        };
+
+        // Forget the evicted modules that have been garbage collected.
+        self.evicted.retain(|(module, _)| !module.is_empty());
+
+        while self.cached_modules() > limit {
+            let path = self
+                .index
+                .keys()
+                .filter(|path| self.is_evictable(path))
+                .min_by_key(|path| self.last_used.get(*path).copied().unwrap_or_default())
+                .cloned();
+
+            let path = match path {
+                Some(path) => path,
+                None => return,
+            };
+
+            // Note: The module itself is freed once nothing references it,
+            // but it can still access `import.meta` meanwhile.
+            if let Some(module) = self.index.remove(&path) {
+                let module = v8::Weak::new(isolate, &module);
+                self.evicted.push((module, path.clone()));
+            }
+
+            // The modules it imports are no longer needed by it.
+            for specifier in self.imports.remove(&path).unwrap_or_default() {
+                match self.importers.get_mut(&specifier) {
+                    Some(count) if *count > 1 => *count -= 1,
+                    _ => {
+                        self.importers.remove(&specifier);
+                    }
+                }
+            }
+
+            self.importers.remove(&path);
+            self.seen.remove(&path);
+            self.last_used.remove(&path);
+        }
+    }
+}
+
+// Checks if a module is built into the runtime (e.g. `console`).
+fn is_core_module(path: &str) -> bool {
+    CORE_MODULES.contains_key(path) || path.starts_with("dune:")
 }
 
 #[derive(Debug, Clone)]
@@ -323,6 +440,7 @@ impl JsFuture for EsModuleFuture {
             true => !state.options.test_mode || state.options.reload,
             false => state.options.reload,
         };
+        let max_size = state.options.max_module_size;
 
         let mut dependencies = vec![];
 
@@ -345,10 +463,22 @@ impl JsFuture for EsModuleFuture {
                 }
             };
 
+            // Module requests store the import attributes as (key, value, location) triples.
+            let attributes = request.get_import_attributes();
+            if let Err(e) = validate_import_attributes(tc_scope, &specifier, attributes, 3) {
+                self.handle_failure(e);
+                return;
+            }
+
+            state.module_map.add_import(&self.path, &specifier);
+
             // Check if requested module has been seen already.
             let seen_module = state.module_map.seen.get(&specifier);
             let status = match seen_module {
-                Some(ModuleStatus::Ready) => continue,
+                Some(ModuleStatus::Ready) => {
+                    state.module_map.touch(&specifier);
+                    continue;
+                }
                 Some(_) => ModuleStatus::Duplicate,
                 None => ModuleStatus::Fetching,
             };
@@ -369,7 +499,7 @@ impl JsFuture for EsModuleFuture {
             if seen_module.is_none() {
                 let task = {
                     let specifier = specifier.clone();
-                    move || match load_import(&specifier, skip_cache) {
+                    move || match load_import(&specifier, skip_cache, max_size) {
                         Ok(source) => Some(Ok(bincode::serialize(&source).unwrap())),
                         Err(e) => Some(Result::Err(e)),
                     }
@@ -431,7 +561,7 @@ pub fn resolve_import(
         match (is_core_module_import, is_url_import) {
             (true, _) if !ignore_core_modules => Box::new(CoreModuleLoader),
             (_, true) => Box::<UrlModuleLoader>::default(),
-            _ => Box::new(FsModuleLoader),
+            _ => Box::<FsModuleLoader>::default(),
         }
     };
 
@@ -439,8 +569,27 @@ pub fn resolve_import(
     loader.resolve(base, &specifier)
 }
 
-/// Loads an import using the appropriate loader.
-pub fn load_import(specifier: &str, skip_cache: bool) -> Result<ModuleSource> {
+/// Resolves an entry import, rooting relative specifiers at `base_dir` (when
+/// provided) instead of the process's current working directory.
+pub fn resolve_entry_import(
+    base_dir: Option<&Path>,
+    specifier: &str,
+    ignore_core_modules: bool,
+    import_map: Option<ImportMap>,
+) -> Result<ModulePath> {
+    // Note: Relative imports resolve against the importing module's directory,
+    // so we're acting like the import comes from a module in `base_dir`.
+    let base = base_dir.map(|dir| dir.join("<entry>").to_string_lossy().into_owned());
+    resolve_import(base.as_deref(), specifier, ignore_core_modules, import_map)
+}
+
+/// Loads an import using the appropriate loader (optionally limiting the size
+/// of the module's source).
+pub fn load_import(
+    specifier: &str,
+    skip_cache: bool,
+    max_size: Option<u64>,
+) -> Result<ModuleSource> {
     // Look the params and choose a loader.
     let loader: Box<dyn ModuleLoader> = match (
         CORE_MODULES.contains_key(specifier),
@@ -448,9 +597,12 @@ pub fn load_import(specifier: &str, skip_cache: bool) -> Result<ModuleSource> {
         Url::parse(specifier).is_ok(),
     ) {
         (true, _, _) => Box::new(CoreModuleLoader),
-        (_, true, _) => Box::new(FsModuleLoader),
-        (_, _, true) => Box::new(UrlModuleLoader { skip_cache }),
-        _ => Box::new(FsModuleLoader),
+        (_, true, _) => Box::new(FsModuleLoader { max_size }),
+        (_, _, true) => Box::new(UrlModuleLoader {
+            skip_cache,
+            max_size,
+        }),
+        _ => Box::new(FsModuleLoader { max_size }),
     };
 
     // Load module.
@@ -519,6 +671,44 @@ impl ImportMap {
     }
 }
 
+/// Makes sure JSON modules are imported using the `type: "json"` attribute.
+pub fn validate_import_attributes(
+    scope: &mut v8::HandleScope,
+    specifier: &str,
+    attributes: v8::Local<v8::FixedArray>,
+    entry_size: usize,
+) -> Result<()> {
+    // Find the value of the `type` attribute (if exists).
+    let mut import_type = None;
+    for i in (0..attributes.length()).step_by(entry_size) {
+        let key = attributes.get(scope, i).unwrap();
+        let key = v8::Local::<v8::String>::try_from(key).unwrap();
+        if key.to_rust_string_lossy(scope) == "type" {
+            let value = attributes.get(scope, i + 1).unwrap();
+            let value = v8::Local::<v8::String>::try_from(value).unwrap();
+            import_type = Some(value.to_rust_string_lossy(scope));
+        }
+    }
+
+    // Note: The query and fragment (e.g. `data.json?v=1`) aren't part of the path.
+    let path = specifier.split(['?', '#']).next().unwrap_or_default();
+    let is_json_module = Path::new(path)
+        .extension()
+        .map(|ext| ext == "json")
+        .unwrap_or_default();
+
+    match (is_json_module, import_type.as_deref()) {
+        (true, Some("json")) | (false, None) => Ok(()),
+        (true, _) => bail!(
+            "Module \"{specifier}\" is a JSON module and must be imported with the `type: \"json\"` attribute."
+        ),
+        (false, Some("json")) => bail!(
+            "Module \"{specifier}\" is not a JSON module but was imported with the `type: \"json\"` attribute."
+        ),
+        (false, Some(value)) => bail!("Unsupported import type \"{value}\" for \"{specifier}\"."),
+    }
+}
+
 /// Resolves module imports synchronously.
 /// https://source.chromium.org/chromium/v8/v8.git/+/51e736ca62bd5c7bfd82488a5587fed31dbf45d5:src/d8.cc;l=741
 pub fn fetch_module_tree<'a>(
@@ -533,7 +723,10 @@ pub fn fetch_module_tree<'a>(
     // Find appropriate loader if source is empty.
     let source = match source {
         Some(source) => source.into(),
-        None => unwrap_or_exit(load_import(filename, true)),
+        None => {
+            let max_size = state.borrow().options.max_module_size;
+            unwrap_or_exit(load_import(filename, true, max_size))
+        }
     };
     let source = v8::String::new(scope, &source).unwrap();
     let mut source = v8::script_compiler::Source::new(source, Some(&origin));
@@ -558,6 +751,15 @@ pub fn fetch_module_tree<'a>(
         let specifier = request.get_specifier().to_rust_string_lossy(scope);
         let specifier = unwrap_or_exit(resolve_import(Some(filename), &specifier, false, None));
 
+        // Module requests store the import attributes as (key, value, location) triples.
+        let attributes = request.get_import_attributes();
+        unwrap_or_exit(validate_import_attributes(scope, &specifier, attributes, 3));
+
+        state
+            .borrow_mut()
+            .module_map
+            .add_import(filename, &specifier);
+
         // Resolve subtree of modules.
         if !state.borrow().module_map.index.contains_key(&specifier) {
             fetch_module_tree(scope, &specifier, None)?;
@@ -8,6 +8,15 @@ use crate::bindings::set_function_to;
 use crate::bindings::set_property_to;
 use crate::JsRuntime;
 
+/// A User Timing entry (i.e. a mark or a measure) stored by the runtime.
+pub struct PerformanceEntry {
+    pub name: String,
+    pub entry_type: String,
+    pub start_time: f64,
+    pub duration: f64,
+    pub detail: Option<v8::Global<v8::Value>>,
+}
+
 pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     // Create local JS object.
     let target = v8::Object::new(scope);
@@ -24,6 +33,11 @@ pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     // `performance.timeOrigin` - the UNIX timestamp which the current process began.
     set_property_to(scope, performance, "timeOrigin", time_origin.into());
 
+    // Storage of the User Timing entries (used by `performance.mark()` etc).
+    set_function_to(scope, target, "addEntry", add_entry);
+    set_function_to(scope, target, "getEntries", get_entries);
+    set_function_to(scope, target, "clearEntries", clear_entries);
+
     // Return v8 global handle.
     v8::Global::new(scope, target)
 }
@@ -39,3 +53,114 @@ fn now(scope: &mut v8::HandleScope, _args: v8::FunctionCallbackArguments, mut rv
 
     rv.set(elapsed_time.into());
 }
+
+/// Converts a performance entry into a JS object.
+fn entry_to_object<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    entry: &PerformanceEntry,
+) -> v8::Local<'s, v8::Object> {
+    let target = v8::Object::new(scope);
+
+    let name = v8::String::new(scope, &entry.name).unwrap();
+    let entry_type = v8::String::new(scope, &entry.entry_type).unwrap();
+    let start_time = v8::Number::new(scope, entry.start_time);
+    let duration = v8::Number::new(scope, entry.duration);
+    let detail = match entry.detail.as_ref() {
+        Some(detail) => v8::Local::new(scope, detail),
+        None => v8::null(scope).into(),
+    };
+
+    set_property_to(scope, target, "name", name.into());
+    set_property_to(scope, target, "entryType", entry_type.into());
+    set_property_to(scope, target, "startTime", start_time.into());
+    set_property_to(scope, target, "duration", duration.into());
+    set_property_to(scope, target, "detail", detail);
+
+    target
+}
+
+/// Returns the value of an (optional) string argument.
+fn get_optional_string(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> Option<String> {
+    match value.is_null_or_undefined() {
+        true => None,
+        false => Some(value.to_rust_string_lossy(scope)),
+    }
+}
+
+/// Stores a new performance entry, returning it as a JS object.
+fn add_entry(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let detail = args.get(4);
+    let entry = PerformanceEntry {
+        name: args.get(0).to_rust_string_lossy(scope),
+        entry_type: args.get(1).to_rust_string_lossy(scope),
+        start_time: args.get(2).number_value(scope).unwrap_or_default(),
+        duration: args.get(3).number_value(scope).unwrap_or_default(),
+        detail: match detail.is_null_or_undefined() {
+            true => None,
+            false => Some(v8::Global::new(scope, detail)),
+        },
+    };
+
+    rv.set(entry_to_object(scope, &entry).into());
+
+    let state_rc = JsRuntime::state(scope);
+    state_rc.borrow_mut().performance_entries.push(entry);
+}
+
+/// Returns the stored entries (optionally filtered by name and type),
+/// in chronological order.
+fn get_entries(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let name = get_optional_string(scope, args.get(0));
+    let entry_type = get_optional_string(scope, args.get(1));
+
+    let state_rc = JsRuntime::state(scope);
+    let state = state_rc.borrow();
+
+    let mut entries: Vec<&PerformanceEntry> = state
+        .performance_entries
+        .iter()
+        .filter(|entry| name.as_deref().is_none_or(|name| entry.name == name))
+        .filter(|entry| {
+            entry_type
+                .as_deref()
+                .is_none_or(|kind| entry.entry_type == kind)
+        })
+        .collect();
+
+    // Note: The sort is stable, so entries with the same start time keep
+    // the order they were added in.
+    entries.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let entries: Vec<v8::Local<v8::Value>> = entries
+        .into_iter()
+        .map(|entry| entry_to_object(scope, entry).into())
+        .collect();
+
+    rv.set(v8::Array::new_with_elements(scope, &entries).into());
+}
+
+/// Removes the stored entries of a type (all of them, or only those with
+/// the given name).
+fn clear_entries(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+) {
+    let entry_type = args.get(0).to_rust_string_lossy(scope);
+    let name = get_optional_string(scope, args.get(1));
+
+    let state_rc = JsRuntime::state(scope);
+    let mut state = state_rc.borrow_mut();
+
+    state.performance_entries.retain(|entry| {
+        entry.entry_type != entry_type || name.as_deref().is_some_and(|name| entry.name != name)
+    });
+}
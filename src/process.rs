@@ -9,6 +9,7 @@ use crate::bindings::set_function_to;
 use crate::bindings::set_property_to;
 use crate::bindings::throw_exception;
 use crate::bindings::BINDINGS;
+use crate::coverage;
 use crate::JsRuntime;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
@@ -34,11 +35,15 @@ pub fn initialize<'s>(
     set_function_to(scope, process, "cwd", cwd);
     set_function_to(scope, process, "exit", exit);
     set_function_to(scope, process, "memoryUsage", memory_usage);
+    set_function_to(scope, process, "cpuUsage", cpu_usage);
     set_function_to(scope, process, "nextTick", next_tick);
     set_function_to(scope, process, "uptime", uptime);
     set_function_to(scope, process, "kill", kill);
     set_function_to(scope, process, "binding", bind);
 
+    #[cfg(target_family = "unix")]
+    set_function_to(scope, process, "getuid", getuid);
+
     process
 }
 
@@ -120,6 +125,9 @@ fn cwd(scope: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, mut rv: v8
 
 /// Exits the program with the given code.
 fn exit(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
+    // Write the coverage report (if requested) before exiting.
+    coverage::flush(scope);
+
     // Exit the program when value is not valid i32.
     match args.get(0).to_int32(scope) {
         Some(code) => std::process::exit(code.value()),
@@ -150,6 +158,43 @@ fn memory_usage(
     rv.set(memory_usage.into());
 }
 
+/// Returns the user and system CPU time (in microseconds) used by the process.
+#[cfg(target_family = "unix")]
+fn cpu_usage(
+    scope: &mut v8::HandleScope,
+    _: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    use nix::sys::resource::getrusage;
+    use nix::sys::resource::UsageWho;
+    use nix::sys::time::TimeValLike;
+
+    let usage = match getrusage(UsageWho::RUSAGE_SELF) {
+        Ok(usage) => usage,
+        Err(e) => {
+            throw_exception(scope, &e.into());
+            return;
+        }
+    };
+
+    let user = v8::Number::new(scope, usage.user_time().num_microseconds() as f64);
+    let system = v8::Number::new(scope, usage.system_time().num_microseconds() as f64);
+
+    let cpu_usage = v8::Object::new(scope);
+
+    set_property_to(scope, cpu_usage, "user", user.into());
+    set_property_to(scope, cpu_usage, "system", system.into());
+
+    rv.set(cpu_usage.into());
+}
+
+#[cfg(target_family = "windows")]
+fn cpu_usage(scope: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
+    use anyhow::anyhow;
+    let err = anyhow!("Reading the CPU usage is not supported on Windows.");
+    throw_exception(scope, &err);
+}
+
 /// Adds callback to the "next tick queue".
 fn next_tick(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
     // Make a global handle out the the function.
@@ -215,6 +260,13 @@ fn kill(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, _: v8:
     }
 }
 
+/// Returns the numeric user identity of the process (Unix only).
+#[cfg(target_family = "unix")]
+fn getuid(scope: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, mut rv: v8::ReturnValue) {
+    let uid = nix::unistd::getuid().as_raw();
+    rv.set(v8::Integer::new_from_unsigned(scope, uid).into());
+}
+
 #[cfg(target_family = "windows")]
 fn kill(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
     // Get PID argument.
@@ -1,4 +1,7 @@
 use crate::bindings;
+use crate::coverage;
+use crate::coverage::CoverageCollector;
+use crate::coverage::ScriptCoverage;
 use crate::errors::report_and_exit;
 use crate::errors::unwrap_or_exit;
 use crate::errors::JsError;
@@ -12,26 +15,36 @@ use crate::inspector::JsRuntimeInspector;
 use crate::modules::create_origin;
 use crate::modules::fetch_module_tree;
 use crate::modules::load_import;
-use crate::modules::resolve_import;
+use crate::modules::resolve_entry_import;
 use crate::modules::EsModuleFuture;
 use crate::modules::ImportKind;
 use crate::modules::ImportMap;
 use crate::modules::ModuleGraph;
 use crate::modules::ModuleMap;
 use crate::modules::ModuleStatus;
+use crate::perf_hooks::PerformanceEntry;
 use crate::process;
 use anyhow::bail;
 use anyhow::Error;
 use anyhow::Ok;
 use dune_event_loop::EventLoop;
+use dune_event_loop::Index;
 use dune_event_loop::LoopHandle;
 use dune_event_loop::LoopInterruptHandle;
 use dune_event_loop::TaskResult;
 use std::cell::RefCell;
 use std::cmp;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::net::SocketAddrV4;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::sync::Once;
+use std::sync::Arc;
+use std::sync::Barrier;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::task::Poll;
+use std::thread;
 use std::time::Instant;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
@@ -69,6 +82,14 @@ pub struct JsRuntimeState {
     pub options: JsRuntimeOptions,
     /// Tracks wake event for current loop iteration.
     pub wake_event_queued: bool,
+    /// Collects precise code coverage (if requested).
+    pub coverage: Option<CoverageCollector>,
+    /// Holds the User Timing entries (marks and measures).
+    pub performance_entries: Vec<PerformanceEntry>,
+    /// The value thrown by the last uncaught exception (or unhandled rejection).
+    pub last_exception: Option<v8::Global<v8::Value>>,
+    /// Holds when each scheduled timer is next due to fire.
+    pub timer_deadlines: HashMap<Index, Instant>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -84,12 +105,102 @@ pub struct JsRuntimeOptions {
     pub import_map: Option<ImportMap>,
     // The numbers of threads used by the thread-pool.
     pub num_threads: Option<usize>,
+    // Starts every thread of the thread-pool when the runtime is created.
+    pub prewarm_thread_pool: bool,
     // Indicates if we're running JavaScript tests.
     pub test_mode: bool,
     // Defines the inspector listening options.
     pub inspect: Option<(SocketAddrV4, bool)>,
     // Exposes v8's garbage collector.
     pub expose_gc: bool,
+    // Collects precise code coverage, writing an LCOV report to this directory.
+    pub coverage: Option<PathBuf>,
+    // Resolves relative entry specifiers against this directory (instead of the cwd).
+    pub resolver_base: Option<PathBuf>,
+    // The maximum size (in bytes) of a module's source (downloads are capped at 10MB by default).
+    pub max_module_size: Option<u64>,
+    // The maximum number of modules kept cached (evicting the least recently used).
+    pub max_cached_modules: Option<usize>,
+    // The maximum number of unhandled rejections kept (when exceeded, the oldest are
+    // reported on the next exceptions check).
+    pub max_promise_rejections: Option<usize>,
+}
+
+/// The V8 flags the process was initialized with.
+static V8_FLAGS: OnceLock<String> = OnceLock::new();
+
+/// Returns the V8 flags matching the provided runtime options.
+fn v8_flags(options: &JsRuntimeOptions) -> String {
+    let mut flags = String::from(concat!(
+        " --no-validate-asm",
+        " --turbo_fast_api_calls",
+        " --harmony-temporal",
+        " --js-float16array",
+    ));
+
+    if let Some(seed) = options.seed {
+        flags.push_str(&format!(" --predictable --random-seed={seed}"))
+    }
+
+    if options.expose_gc {
+        flags.push_str(" --expose-gc")
+    }
+
+    flags
+}
+
+/// Initializes the V8 platform, exactly once per process.
+///
+/// V8 flags are process-wide and can't be changed safely once the engine is
+/// initialized (or while another thread is creating an isolate), so they're
+/// set along with the platform, using the options of the first runtime.
+fn initialize_v8(options: &JsRuntimeOptions) {
+    let flags = v8_flags(options);
+
+    // Note: Concurrent callers block here until the initialization completes.
+    let applied = V8_FLAGS.get_or_init(|| {
+        v8::V8::set_flags_from_string(&flags);
+        let platform = v8::new_default_platform(0, false).make_shared();
+        v8::V8::initialize_platform(platform);
+        v8::V8::initialize();
+        flags.clone()
+    });
+
+    if *applied != flags {
+        eprintln!("Warning: V8 is already initialized, ignoring the flags \"{flags}\".");
+    }
+}
+
+/// Returns the number of threads the thread-pool is pre-warmed with by default.
+fn default_pool_size() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Runs a task on every thread of the pool, waiting until all of them have
+/// started, so the first async operations don't pay for spawning threads.
+///
+/// Returns the ids of the threads that were started.
+fn prewarm_thread_pool(event_loop: &EventLoop, size: usize) -> HashSet<thread::ThreadId> {
+    let barrier = Arc::new(Barrier::new(size + 1));
+    let threads = Arc::new(Mutex::new(HashSet::new()));
+
+    for _ in 0..size {
+        let barrier = barrier.clone();
+        let threads = threads.clone();
+        let task = move || {
+            threads.lock().unwrap().insert(thread::current().id());
+            barrier.wait();
+            None
+        };
+        event_loop
+            .handle()
+            .spawn(task, Some(|_: LoopHandle, _: TaskResult| {}));
+    }
+
+    barrier.wait();
+
+    let threads = threads.lock().unwrap();
+    threads.clone()
 }
 
 pub struct JsRuntime {
@@ -113,31 +224,8 @@ impl JsRuntime {
 
     /// Creates a new JsRuntime based on provided options.
     pub fn with_options(options: JsRuntimeOptions) -> JsRuntime {
-        // Configuration flags for V8.
-        let mut flags = String::from(concat!(
-            " --no-validate-asm",
-            " --turbo_fast_api_calls",
-            " --harmony-temporal",
-            " --js-float16array",
-        ));
-
-        if let Some(seed) = options.seed {
-            flags.push_str(&format!(" --predictable --random-seed={seed}"))
-        }
-
-        if options.expose_gc {
-            flags.push_str(" --expose-gc")
-        }
-
-        v8::V8::set_flags_from_string(&flags);
-
         // Fire up the v8 engine.
-        static V8_INIT: Once = Once::new();
-        V8_INIT.call_once(move || {
-            let platform = v8::new_default_platform(0, false).make_shared();
-            v8::V8::initialize_platform(platform);
-            v8::V8::initialize();
-        });
+        initialize_v8(&options);
 
         let mut isolate = v8::Isolate::new(v8::CreateParams::default());
 
@@ -157,19 +245,31 @@ impl JsRuntime {
 
         const MIN_POOL_SIZE: usize = 1;
 
-        let event_loop = match options.num_threads {
+        // Note: Pre-warming has to know the size of the pool, so it's set
+        // explicitly (to the available parallelism, unless specified).
+        let num_threads = match options.prewarm_thread_pool {
+            true => options.num_threads.or_else(|| Some(default_pool_size())),
+            false => options.num_threads,
+        };
+
+        let event_loop = match num_threads {
             Some(n) => EventLoop::new(cmp::max(n, MIN_POOL_SIZE)),
             None => EventLoop::default(),
         };
 
+        if let (true, Some(n)) = (options.prewarm_thread_pool, num_threads) {
+            prewarm_thread_pool(&event_loop, cmp::max(n, MIN_POOL_SIZE));
+        }
+
         let time_origin = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis();
 
-        // Initialize the v8 inspector.
+        // Initialize the v8 inspector (coverage is also collected through it).
         let address = options.inspect.map(|(address, _)| (address));
-        let inspector = options.inspect.map(|(_, waiting_for_session)| {
+        let inspector = (options.inspect.is_some() || options.coverage.is_some()).then(|| {
+            let waiting_for_session = options.inspect.is_some_and(|(_, waiting)| waiting);
             JsRuntimeInspector::new(
                 &mut isolate,
                 context.clone(),
@@ -179,20 +279,33 @@ impl JsRuntime {
             )
         });
 
+        // Start collecting precise coverage before any script gets compiled.
+        let coverage = match (options.coverage.as_ref(), inspector.as_ref()) {
+            (Some(_), Some(inspector)) => {
+                let session = inspector.borrow().connect_local_session();
+                Some(unwrap_or_exit(CoverageCollector::start(session)))
+            }
+            _ => None,
+        };
+
         // Store state inside the v8 isolate slot.
         // https://v8docs.nodesource.com/node-4.8/d5/dda/classv8_1_1_isolate.html#a7acadfe7965997e9c386a05f098fbe36
         let state = Rc::new(RefCell::new(JsRuntimeState {
             context,
-            module_map: ModuleMap::new(),
+            module_map: ModuleMap::new(options.max_cached_modules),
             handle: event_loop.handle(),
             interrupt_handle: event_loop.interrupt_handle(),
             pending_futures: Vec::new(),
             startup_moment: Instant::now(),
             time_origin,
             next_tick_queue: Vec::new(),
-            exceptions: ExceptionState::new(),
+            exceptions: ExceptionState::new(options.max_promise_rejections),
             options,
             wake_event_queued: false,
+            coverage,
+            performance_entries: Vec::new(),
+            last_exception: None,
+            timer_deadlines: HashMap::new(),
         }));
 
         isolate.set_slot(state.clone());
@@ -207,8 +320,7 @@ impl JsRuntime {
         runtime.load_main_environment();
 
         // Start inspector agent is requested.
-        if let Some(inspector) = runtime.inspector().as_mut() {
-            let address = address.unwrap();
+        if let (Some(inspector), Some(address)) = (runtime.inspector(), address) {
             inspector.borrow_mut().start_agent(address);
         }
 
@@ -275,6 +387,13 @@ impl JsRuntime {
 
         let handle_exception =
             |scope: &mut v8::TryCatch<'_, v8::HandleScope<'_>>| -> ExecuteScriptResult {
+                // Note: Terminations (e.g. through a thread-safe handle) can't be
+                // handled by JavaScript, cancel it so the runtime can be reused.
+                if scope.has_terminated() {
+                    scope.cancel_terminate_execution();
+                    bail!("Execution terminated");
+                }
+
                 // Extract the exception during compilation.
                 assert!(scope.has_caught());
                 let exception = scope.exception().unwrap();
@@ -312,7 +431,10 @@ impl JsRuntime {
         // location passed as parameter as an ES module.
         let path = match source.is_some() {
             true => filename.to_string(),
-            false => unwrap_or_exit(resolve_import(None, filename, false, None)),
+            false => {
+                let base_dir = state.options.resolver_base.as_deref();
+                unwrap_or_exit(resolve_entry_import(base_dir, filename, false, None))
+            }
         };
 
         // Create static import module graph.
@@ -337,7 +459,8 @@ impl JsRuntime {
 
         let task = {
             let specifier = path.clone();
-            move || match load_import(&specifier, true) {
+            let max_size = state.options.max_module_size;
+            move || match load_import(&specifier, true, max_size) {
                 anyhow::Result::Ok(source) => Some(Ok(bincode::serialize(&source).unwrap())),
                 Err(e) => Some(Result::Err(e)),
             }
@@ -361,12 +484,36 @@ impl JsRuntime {
         Ok(())
     }
 
+    /// Executes the program's entry point (setting `import.meta.main`) and runs
+    /// the event-loop until no more pending events exists.
+    pub fn execute_main_module(&mut self, specifier: &str) -> Result<(), Error> {
+        // Resolve the entry point using the runtime's import-map.
+        let state_rc = self.get_state();
+        let path = {
+            let state = state_rc.borrow();
+            let base_dir = state.options.resolver_base.as_deref();
+            let import_map = state.options.import_map.clone();
+            resolve_entry_import(base_dir, specifier, true, import_map)?
+        };
+
+        // Mark the module as the program's main entry point.
+        state_rc.borrow_mut().module_map.main = Some(path.clone());
+
+        self.execute_module(&path, None)?;
+        self.run_event_loop();
+
+        Ok(())
+    }
+
     /// Runs a single tick of the event-loop.
     pub fn tick_event_loop(&mut self) {
         run_next_tick_callbacks(&mut self.handle_scope());
         self.fast_forward_imports();
         self.event_loop.tick();
         self.run_pending_futures();
+        // Drain the micro-tasks queued by the evaluated modules before the
+        // unhandled rejections get checked.
+        run_next_tick_callbacks(&mut self.handle_scope());
     }
 
     /// Polls the inspector for new devtools messages.
@@ -383,12 +530,7 @@ impl JsRuntime {
         // Run callbacks/promises from next-tick and micro-task queues.
         run_next_tick_callbacks(&mut self.handle_scope());
 
-        while self.event_loop.has_pending_events()
-            || self.has_promise_rejections()
-            || self.isolate.has_pending_background_tasks()
-            || self.has_pending_imports()
-            || self.has_next_tick_callbacks()
-        {
+        while self.has_pending_work() {
             // Check for pending devtools messages.
             self.poll_inspect_session();
             // Tick the event-loop one cycle.
@@ -400,6 +542,49 @@ impl JsRuntime {
             }
         }
 
+        self.finish_event_loop();
+    }
+
+    /// Runs a single iteration of the event-loop without blocking, returning
+    /// `Poll::Pending` while there is still pending work.
+    ///
+    /// Note: This is meant for embedders driving the runtime from their own
+    /// loop, which are responsible for calling it again (by the deadline
+    /// returned from `next_timer_deadline`, or the next I/O event).
+    pub fn poll_event_loop(&mut self) -> Poll<Result<(), Error>> {
+        // Check for pending devtools messages.
+        self.poll_inspect_session();
+
+        // Note: Interrupting the event-loop beforehand makes the tick's poll
+        // phase return right away, instead of waiting for new events.
+        self.event_loop.interrupt_handle().interrupt();
+        self.tick_event_loop();
+
+        // Report any unhandled promise rejections.
+        if let Some(error) = check_exceptions(&mut self.handle_scope()) {
+            return Poll::Ready(Err(error.into()));
+        }
+
+        if self.has_pending_work() {
+            return Poll::Pending;
+        }
+
+        self.finish_event_loop();
+        Poll::Ready(Ok(()))
+    }
+
+    /// Returns when the earliest scheduled timer is due (if any).
+    pub fn next_timer_deadline(&self) -> Option<Instant> {
+        let state_rc = self.get_state();
+        let state = state_rc.borrow();
+        state.timer_deadlines.values().min().copied()
+    }
+
+    /// Wraps up the program once the event-loop has no more pending work.
+    fn finish_event_loop(&mut self) {
+        // Write the coverage report while the context is still alive.
+        coverage::flush(&mut self.handle_scope());
+
         // We can now notify debugger that the program has finished running
         // and we're ready to exit the process.
         if let Some(inspector) = self.inspector() {
@@ -409,6 +594,15 @@ impl JsRuntime {
         }
     }
 
+    // Returns if the event-loop (or v8) has still work to do.
+    fn has_pending_work(&mut self) -> bool {
+        self.event_loop.has_pending_events()
+            || self.has_promise_rejections()
+            || self.isolate.has_pending_background_tasks()
+            || self.has_pending_imports()
+            || self.has_next_tick_callbacks()
+    }
+
     /// Runs all the pending javascript tasks.
     fn run_pending_futures(&mut self) {
         // Get a handle-scope and a reference to the runtime's state.
@@ -427,10 +621,12 @@ impl JsRuntime {
 
         for mut fut in futures {
             fut.run(scope);
+            // Note: Exceptions are checked after the micro-task checkpoint, so a
+            // rejection handler attached later in the same turn still counts.
+            run_next_tick_callbacks(scope);
             if let Some(error) = check_exceptions(scope) {
                 report_and_exit(error);
             }
-            run_next_tick_callbacks(scope);
         }
 
         state_rc.borrow_mut().wake_event_queued = false;
@@ -543,6 +739,9 @@ impl JsRuntime {
             }
         }
 
+        // Drop the least recently used modules (if the module cache is bounded).
+        state_rc.borrow_mut().module_map.evict(scope);
+
         // Note: It's important to perform a nextTick checkpoint at this
         // point to allow resources behind a promise to be scheduled correctly
         // to the event-loop.
@@ -563,6 +762,22 @@ impl JsRuntime {
     pub fn has_next_tick_callbacks(&mut self) -> bool {
         !self.get_state().borrow().next_tick_queue.is_empty()
     }
+
+    /// Returns the per-script coverage collected so far.
+    pub fn coverage_report(&mut self) -> Result<Vec<ScriptCoverage>, Error> {
+        let state_rc = self.get_state();
+        let collector = state_rc.borrow_mut().coverage.take();
+
+        // Note: The state isn't borrowed while V8 answers the CDP commands.
+        let mut collector = match collector {
+            Some(collector) => collector,
+            None => bail!("Code coverage is not being collected."),
+        };
+
+        let report = collector.take_report();
+        state_rc.borrow_mut().coverage = Some(collector);
+        report
+    }
 }
 
 impl std::ops::Drop for JsRuntime {
@@ -572,6 +787,7 @@ impl std::ops::Drop for JsRuntime {
         // and allowing the the OS to handle memory purging at the program's
         // termination resolves the issue.
         std::mem::forget(self.inspector.take());
+        std::mem::forget(self.get_state().borrow_mut().coverage.take());
     }
 }
 
@@ -611,6 +827,28 @@ impl JsRuntime {
     pub fn inspector(&mut self) -> Option<Rc<RefCell<JsRuntimeInspector>>> {
         self.inspector.as_ref().cloned()
     }
+
+    /// Returns a handle that can terminate the runtime's execution from other threads
+    /// (e.g. watchdogs enforcing a time limit).
+    ///
+    /// Unlike the runtime itself, the handle is `Send` and `Sync`, but only its
+    /// thread-safe methods (`terminate_execution`, `cancel_terminate_execution` and
+    /// `is_execution_terminating`) should be used. Calling them after the runtime has
+    /// been dropped has no effect. The termination can't be caught by scripts.
+    ///
+    /// Only `execute_script` recovers from a termination: it cancels it and returns
+    /// an "Execution terminated" error, leaving the runtime usable. A termination
+    /// landing in the event-loop (timers, next-tick callbacks or module evaluation)
+    /// is reported as an uncaught exception instead.
+    pub fn thread_safe_handle(&self) -> v8::IsolateHandle {
+        self.isolate.thread_safe_handle()
+    }
+
+    /// Takes the raw value thrown by the last uncaught exception (or unhandled
+    /// rejection), complementing the `JsError` returned by the failed call.
+    pub fn take_last_exception(&mut self) -> Option<v8::Global<v8::Value>> {
+        self.get_state().borrow_mut().last_exception.take()
+    }
 }
 
 /// Runs callbacks stored in the next-tick queue.
@@ -673,25 +911,30 @@ pub fn check_exceptions(scope: &mut v8::HandleScope) -> Option<JsError> {
             if tc_scope.has_caught() {
                 let exception = tc_scope.exception().unwrap();
                 let exception = v8::Local::new(tc_scope, exception);
-                let error = JsError::from_v8_exception(tc_scope, exception, None);
-                return Some(error);
+                return Some(uncaught_error(tc_scope, exception, None));
             }
 
             return None;
         }
 
-        let error = JsError::from_v8_exception(scope, exception, None);
-        return Some(error);
+        drop(state);
+        return Some(uncaught_error(scope, exception, None));
     }
 
-    let promise_rejections: Vec<PromiseRejectionEntry> = state_rc
-        .borrow_mut()
-        .exceptions
-        .promise_rejections
-        .drain(..)
-        .collect();
+    let promise_rejections = state_rc.borrow_mut().exceptions.take_promise_rejections();
 
     // Then, check for unhandled rejections.
+    report_promise_rejections(scope, promise_rejections)
+}
+
+// Reports unhandled promise rejections (through the process hooks, if set),
+// returning an error for the first one that isn't handled.
+fn report_promise_rejections(
+    scope: &mut v8::HandleScope,
+    promise_rejections: Vec<PromiseRejectionEntry>,
+) -> Option<JsError> {
+    let state_rc = JsRuntime::state(scope);
+
     for (promise, exception) in promise_rejections.iter() {
         let state = state_rc.borrow_mut();
         let promise = v8::Local::new(scope, promise);
@@ -711,8 +954,7 @@ pub fn check_exceptions(scope: &mut v8::HandleScope) -> Option<JsError> {
             if tc_scope.has_caught() {
                 let exception = tc_scope.exception().unwrap();
                 let exception = v8::Local::new(tc_scope, exception);
-                let error = JsError::from_v8_exception(tc_scope, exception, None);
-                return Some(error);
+                return Some(uncaught_error(tc_scope, exception, None));
             }
 
             continue;
@@ -733,18 +975,299 @@ pub fn check_exceptions(scope: &mut v8::HandleScope) -> Option<JsError> {
             if tc_scope.has_caught() {
                 let exception = tc_scope.exception().unwrap();
                 let exception = v8::Local::new(tc_scope, exception);
-                let error = JsError::from_v8_exception(tc_scope, exception, None);
-                return Some(error);
+                return Some(uncaught_error(tc_scope, exception, None));
             }
 
             continue;
         }
 
+        drop(state);
         let prefix = Some("(in promise) ");
-        let error = JsError::from_v8_exception(scope, exception, prefix);
-
-        return Some(error);
+        return Some(uncaught_error(scope, exception, prefix));
     }
 
     None
 }
+
+// Converts an uncaught exception into a `JsError`, keeping the thrown value
+// for embedders (see `JsRuntime::take_last_exception`).
+fn uncaught_error(
+    scope: &mut v8::HandleScope,
+    exception: v8::Local<v8::Value>,
+    prefix: Option<&str>,
+) -> JsError {
+    let state_rc = JsRuntime::state(scope);
+    state_rc.borrow_mut().last_exception = Some(v8::Global::new(scope, exception));
+    JsError::from_v8_exception(scope, exception, prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execute_main_module_runs_event_loop() {
+        let mut runtime = JsRuntime::new();
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/timer.js");
+
+        runtime.execute_main_module(fixture).unwrap();
+
+        // The timer should have fired before the event-loop completed.
+        let value = runtime
+            .execute_script("<anonymous>", "globalThis.timerResult")
+            .unwrap()
+            .unwrap();
+
+        let scope = &mut runtime.handle_scope();
+        let value = v8::Local::new(scope, value);
+
+        assert_eq!(value.to_rust_string_lossy(scope), "main");
+    }
+
+    #[test]
+    fn test_poll_event_loop_until_completion() {
+        let mut runtime = JsRuntime::new();
+        let source = "
+            globalThis.ticks = 0;
+            const tick = () => ++globalThis.ticks < 3 && setTimeout(tick, 5);
+            setTimeout(tick, 5);
+        ";
+
+        runtime.execute_module("poll.js", Some(source)).unwrap();
+
+        // Drive the event-loop manually (without blocking on timers).
+        let mut polls = 0;
+        while runtime.poll_event_loop().is_pending() {
+            polls += 1;
+            // Sleep until the next timer is due, like an external loop would.
+            if let Some(deadline) = runtime.next_timer_deadline() {
+                std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            }
+        }
+
+        let value = runtime
+            .execute_script("<anonymous>", "globalThis.ticks")
+            .unwrap()
+            .unwrap();
+
+        let scope = &mut runtime.handle_scope();
+        let value = v8::Local::new(scope, value);
+
+        assert!(polls >= 3);
+        assert_eq!(value.to_rust_string_lossy(scope), "3");
+    }
+
+    #[test]
+    fn test_resolver_base_roots_relative_entry_imports() {
+        let options = JsRuntimeOptions {
+            resolver_base: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures").into()),
+            ..Default::default()
+        };
+
+        // Note: The specifier doesn't exist relative to the process's cwd.
+        let mut runtime = JsRuntime::with_options(options);
+        runtime.execute_main_module("./timer.js").unwrap();
+
+        let value = runtime
+            .execute_script("<anonymous>", "globalThis.timerResult")
+            .unwrap()
+            .unwrap();
+
+        let scope = &mut runtime.handle_scope();
+        let value = v8::Local::new(scope, value);
+
+        assert_eq!(value.to_rust_string_lossy(scope), "main");
+    }
+
+    #[test]
+    fn test_runtimes_can_be_created_concurrently() {
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    let mut runtime = JsRuntime::new();
+                    let value = runtime
+                        .execute_script("<anonymous>", "typeof Float16Array")
+                        .unwrap()
+                        .unwrap();
+
+                    let scope = &mut runtime.handle_scope();
+                    let value = v8::Local::new(scope, value);
+                    value.to_rust_string_lossy(scope)
+                })
+            })
+            .collect();
+
+        // Every isolate should see the process-wide flags.
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), "function");
+        }
+    }
+
+    #[test]
+    fn test_thread_safe_handle_terminates_execution() {
+        let mut runtime = JsRuntime::new();
+        let handle = runtime.thread_safe_handle();
+
+        // Terminate the (never-ending) script from a watchdog thread.
+        let watchdog = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            handle.terminate_execution();
+        });
+
+        let result = runtime.execute_script("<anonymous>", "while (true) {}");
+        watchdog.join().unwrap();
+
+        assert_eq!(result.err().unwrap().to_string(), "Execution terminated");
+
+        // The runtime can still execute code afterwards.
+        let value = runtime
+            .execute_script("<anonymous>", "1 + 1")
+            .unwrap()
+            .unwrap();
+
+        let scope = &mut runtime.handle_scope();
+        let value = v8::Local::new(scope, value);
+
+        assert_eq!(value.to_rust_string_lossy(scope), "2");
+    }
+
+    #[test]
+    fn test_thread_pool_is_prewarmed_before_the_first_op() {
+        let event_loop = EventLoop::new(3);
+        let warmed = prewarm_thread_pool(&event_loop, 3);
+
+        // The threads already exist before any op is scheduled.
+        assert_eq!(warmed.len(), 3);
+
+        // Every op waits for the rest (and the test), so they can only proceed
+        // if each one runs on a different thread of the pool.
+        let threads = Arc::new(Mutex::new(HashSet::new()));
+        let barrier = Arc::new(Barrier::new(4));
+        let handle = event_loop.handle();
+
+        for _ in 0..3 {
+            let threads = threads.clone();
+            let barrier = barrier.clone();
+            let task = move || {
+                threads.lock().unwrap().insert(thread::current().id());
+                barrier.wait();
+                None
+            };
+            handle.spawn(task, Some(|_: LoopHandle, _: TaskResult| {}));
+        }
+
+        barrier.wait();
+
+        // The ops ran on the pre-warmed threads (no new ones were spawned).
+        assert_eq!(*threads.lock().unwrap(), warmed);
+    }
+
+    #[test]
+    fn test_promise_rejections_buffer_is_bounded() {
+        let options = JsRuntimeOptions {
+            max_promise_rejections: Some(10),
+            ..Default::default()
+        };
+
+        let mut runtime = JsRuntime::with_options(options);
+        let source = "
+            globalThis.reported = 0;
+            process.on('unhandledRejection', () => globalThis.reported++);
+            for (let i = 0; i < 100; i++) Promise.reject(new Error(i));
+        ";
+
+        runtime.execute_script("<anonymous>", source).unwrap();
+
+        // The oldest rejections are queued for reporting, instead of being kept around.
+        let state_rc = runtime.get_state();
+        let pending = state_rc.borrow().exceptions.promise_rejections.len();
+        let overflowed = state_rc.borrow().exceptions.overflowed_rejections.len();
+        assert_eq!((pending, overflowed), (10, 90));
+
+        assert!(check_exceptions(&mut runtime.handle_scope()).is_none());
+
+        let value = runtime
+            .execute_script("<anonymous>", "globalThis.reported")
+            .unwrap()
+            .unwrap();
+
+        let scope = &mut runtime.handle_scope();
+        let value = v8::Local::new(scope, value);
+
+        assert_eq!(value.to_rust_string_lossy(scope), "100");
+    }
+
+    #[test]
+    fn test_take_last_exception_returns_the_thrown_value() {
+        let mut runtime = JsRuntime::new();
+        let source = "throw { code: 'E_CUSTOM', retries: 3 };";
+
+        assert!(runtime.execute_script("<anonymous>", source).is_err());
+
+        let exception = runtime.take_last_exception().unwrap();
+        {
+            let scope = &mut runtime.handle_scope();
+            let exception = v8::Local::new(scope, exception);
+            let exception = v8::Local::<v8::Object>::try_from(exception).unwrap();
+
+            let key = v8::String::new(scope, "code").unwrap();
+            let code = exception.get(scope, key.into()).unwrap();
+
+            assert_eq!(code.to_rust_string_lossy(scope), "E_CUSTOM");
+        }
+
+        // The value is only returned once.
+        assert!(runtime.take_last_exception().is_none());
+    }
+
+    #[test]
+    fn test_module_cache_evicts_least_recently_used_modules() {
+        let dir = std::env::temp_dir().join("dune-module-cache");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let paths: Vec<String> = (0..5)
+            .map(|i| {
+                let path = dir.join(format!("module_{i}.js"));
+                let source =
+                    format!("globalThis.loads = (globalThis.loads ?? 0) + 1;\nexport default {i};");
+                std::fs::write(&path, source).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        // Import every module, then the first one again (after it was evicted).
+        let main = dir.join("main.js");
+        let source = format!(
+            "globalThis.values = [];
+            for (const path of {paths:?}) values.push((await import(path)).default);
+            values.push((await import({first:?})).default);",
+            first = paths[0],
+        );
+
+        std::fs::write(&main, source).unwrap();
+
+        let options = JsRuntimeOptions {
+            max_cached_modules: Some(2),
+            ..Default::default()
+        };
+
+        let mut runtime = JsRuntime::with_options(options);
+        runtime
+            .execute_main_module(&main.to_string_lossy())
+            .unwrap();
+
+        // The main module and the latest import are the only modules left.
+        let state = runtime.get_state();
+        assert_eq!(state.borrow().module_map.cached_modules(), 2);
+
+        let value = runtime
+            .execute_script("<anonymous>", "`${values} (${loads} loads)`")
+            .unwrap()
+            .unwrap();
+
+        let scope = &mut runtime.handle_scope();
+        let value = v8::Local::new(scope, value);
+
+        assert_eq!(value.to_rust_string_lossy(scope), "0,1,2,3,4,0 (6 loads)");
+    }
+}
@@ -1,7 +1,12 @@
 use crate::bindings::set_function_to;
+use crate::bindings::set_property_to;
 use crate::bindings::throw_exception;
 use std::io;
+use std::io::Read;
 use std::io::Write;
+use terminal_size::terminal_size_of;
+use terminal_size::Height;
+use terminal_size::Width;
 
 pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     // Create local JS object.
@@ -10,8 +15,10 @@ pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     set_function_to(scope, target, "write", write);
     set_function_to(scope, target, "writeError", write_error);
     set_function_to(scope, target, "read", read);
+    set_function_to(scope, target, "readBytes", read_bytes);
     set_function_to(scope, target, "clear", clear);
     set_function_to(scope, target, "callConsole", call_console);
+    set_function_to(scope, target, "consoleSize", console_size);
 
     // Return v8 global handle.
     v8::Global::new(scope, target)
@@ -19,11 +26,10 @@ pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
 
 /// Writes data to the stdout stream.
 fn write(scope: &mut v8::HandleScope, args: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
-    // Convert string to bytes.
-    let content = args.get(0).to_rust_string_lossy(scope);
-    let content = content.as_bytes();
+    // Convert string (or bytes) to a byte buffer.
+    let content = bytes_of(scope, args.get(0));
     // Flush bytes to stdout.
-    io::stdout().write_all(content).unwrap();
+    io::stdout().write_all(&content).unwrap();
     io::stdout().flush().unwrap();
 }
 
@@ -33,11 +39,10 @@ fn write_error(
     args: v8::FunctionCallbackArguments,
     _: v8::ReturnValue,
 ) {
-    // Convert string to bytes.
-    let content = args.get(0).to_rust_string_lossy(scope);
-    let content = content.as_bytes();
+    // Convert string (or bytes) to a byte buffer.
+    let content = bytes_of(scope, args.get(0));
     // Flush bytes to stderr.
-    io::stderr().write_all(content).unwrap();
+    io::stderr().write_all(&content).unwrap();
     io::stderr().flush().unwrap();
 }
 
@@ -53,6 +58,47 @@ fn read(scope: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, mut ret:
     ret.set(input.into());
 }
 
+/// Reads (synchronously) the next chunk of bytes from the stdin, returning `null` on EOF.
+fn read_bytes(
+    scope: &mut v8::HandleScope,
+    _: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let mut buffer = vec![0; 64 * 1024];
+    let size = match io::stdin().read(&mut buffer) {
+        Ok(size) => size,
+        Err(e) => {
+            throw_exception(scope, &e.into());
+            return;
+        }
+    };
+
+    if size == 0 {
+        rv.set(v8::null(scope).into());
+        return;
+    }
+
+    // Create ArrayBuffer's backing store from the bytes read.
+    buffer.truncate(size);
+    let store = v8::ArrayBuffer::new_backing_store_from_boxed_slice(buffer.into_boxed_slice());
+    let store = store.make_shared();
+
+    let bytes = v8::ArrayBuffer::with_backing_store(scope, &store);
+    rv.set(bytes.into());
+}
+
+/// Returns the bytes of a string (UTF-8 encoded) or an ArrayBufferView.
+fn bytes_of(scope: &mut v8::HandleScope, value: v8::Local<v8::Value>) -> Vec<u8> {
+    match v8::Local::<v8::ArrayBufferView>::try_from(value) {
+        Ok(view) => {
+            let mut buffer = vec![0; view.byte_length()];
+            view.copy_contents(&mut buffer);
+            buffer
+        }
+        Err(_) => value.to_rust_string_lossy(scope).into_bytes(),
+    }
+}
+
 /// Clears the terminal if the environment allows it.
 fn clear(scope: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, _: v8::ReturnValue) {
     if let Err(e) = clearscreen::clear() {
@@ -60,6 +106,24 @@ fn clear(scope: &mut v8::HandleScope, _: v8::FunctionCallbackArguments, _: v8::R
     }
 }
 
+/// Returns the size (columns and rows) of the terminal, if stdout is attached to one.
+fn console_size(
+    scope: &mut v8::HandleScope,
+    _: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    if let Some((Width(columns), Height(rows))) = terminal_size_of(io::stdout()) {
+        let size = v8::Object::new(scope);
+        let columns = v8::Integer::new(scope, columns as i32);
+        let rows = v8::Integer::new(scope, rows as i32);
+
+        set_property_to(scope, size, "columns", columns.into());
+        set_property_to(scope, size, "rows", rows.into());
+
+        rv.set(size.into());
+    }
+}
+
 /// Native wrapper that will preserve the original stack.
 /// https://github.com/denoland/deno_core/blob/main/core/runtime/bindings.rs#L504-L529
 fn call_console(
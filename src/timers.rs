@@ -2,7 +2,10 @@ use crate::bindings::set_function_to;
 use crate::runtime::JsFuture;
 use crate::runtime::JsRuntime;
 use dune_event_loop::LoopHandle;
+use std::cell::Cell;
 use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
 
 pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
     // Create local JS object.
@@ -46,6 +49,18 @@ impl JsFuture for TimeoutFuture {
     }
 }
 
+/// The maximum delay a timer can have (the max value of a signed 32-bit integer).
+const TIMEOUT_MAX: f64 = i32::MAX as f64;
+
+/// Converts a JS delay into millis, treating negative, NaN and overflowing
+/// values as zero (the timer will fire immediately).
+fn coerce_delay(delay: f64) -> u64 {
+    match delay {
+        delay if !(0.0..=TIMEOUT_MAX).contains(&delay) => 0,
+        delay => delay.trunc() as u64,
+    }
+}
+
 /// Schedules a new timeout to the event-loop.
 fn create_timeout(
     scope: &mut v8::HandleScope,
@@ -57,7 +72,7 @@ fn create_timeout(
     let callback = Rc::new(v8::Global::new(scope, callback));
 
     // Get timer's expiration time in millis.
-    let millis = args.get(1).int32_value(scope).unwrap() as u64;
+    let millis = coerce_delay(args.get(1).number_value(scope).unwrap_or_default());
 
     // Decide if the timer is an interval.
     let repeatable = args.get(2).to_rust_string_lossy(scope) == "true";
@@ -77,10 +92,23 @@ fn create_timeout(
     let state_rc = JsRuntime::state(scope);
     let params = Rc::new(params);
 
+    // Note: The timer's id is only known once it's scheduled.
+    let timer_id = Rc::new(Cell::new(0));
+    let delay = Duration::from_millis(millis);
+
     let timeout_cb = {
         let state_rc = state_rc.clone();
+        let timer_id = timer_id.clone();
         move |_: LoopHandle| {
             let mut state = state_rc.borrow_mut();
+            // Keep the timer's next deadline up to date (it's gone once fired).
+            if repeatable {
+                let deadline = Instant::now() + delay;
+                state.timer_deadlines.insert(timer_id.get(), deadline);
+            } else {
+                state.timer_deadlines.remove(&timer_id.get());
+            }
+
             let future = TimeoutFuture {
                 cb: Rc::clone(&callback),
                 params: Rc::clone(&params),
@@ -98,9 +126,12 @@ fn create_timeout(
     };
 
     // Schedule a new timer to the event-loop.
-    let state = state_rc.borrow();
+    let mut state = state_rc.borrow_mut();
     let id = state.handle.timer(millis, repeatable, timeout_cb);
 
+    timer_id.set(id);
+    state.timer_deadlines.insert(id, Instant::now() + delay);
+
     // Return timeout's internal id.
     rv.set(v8::Number::new(scope, id as f64).into());
 }
@@ -115,7 +146,9 @@ fn remove_timeout(
     let id = args.get(0).int32_value(scope).unwrap() as u32;
     let state_rc = JsRuntime::state(scope);
 
-    state_rc.borrow().handle.remove_timer(&id);
+    let mut state = state_rc.borrow_mut();
+    state.handle.remove_timer(&id);
+    state.timer_deadlines.remove(&id);
 }
 
 struct ImmediateFuture {
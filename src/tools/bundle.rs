@@ -120,7 +120,7 @@ impl Load for Loader<'_> {
         };
 
         // Try load the module's source-code.
-        let source = load_import(&specifier, self.options.skip_cache)?;
+        let source = load_import(&specifier, self.options.skip_cache, None)?;
         let path = FileName::Real(specifier.into());
         let fm = self.cm.new_source_file(path.into(), source);
 
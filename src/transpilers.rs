@@ -60,7 +60,7 @@ impl TypeScript {
             }),
             Default::default(),
             StringInput::from(&*fm),
-            None,
+            Some(&comments),
         );
 
         let mut parser = Parser::new_from(lexer);
@@ -98,7 +98,7 @@ impl TypeScript {
                 let mut emitter = Emitter {
                     cfg: swc_ecma_codegen::Config::default(),
                     cm: cm.clone(),
-                    comments: None,
+                    comments: Some(&comments),
                     wr: JsWriter::new(cm.clone(), "\n", &mut output, Some(&mut source_map)),
                 };
 
@@ -150,7 +150,7 @@ impl Jsx {
             }),
             Default::default(),
             StringInput::from(&*fm),
-            None,
+            Some(&comments),
         );
 
         let mut parser = Parser::new_from(lexer);
@@ -201,7 +201,7 @@ impl Jsx {
                 let mut emitter = Emitter {
                     cfg: swc_ecma_codegen::Config::default(),
                     cm: cm.clone(),
-                    comments: None,
+                    comments: Some(&comments),
                     wr: JsWriter::new(cm.clone(), "\n", &mut output, Some(&mut source_map)),
                 };
 
@@ -0,0 +1,122 @@
+use crate::bindings::set_function_to;
+use crate::bindings::throw_exception;
+use crate::runtime::JsRuntime;
+use anyhow::anyhow;
+use base64::prelude::*;
+
+pub fn initialize(scope: &mut v8::HandleScope) -> v8::Global<v8::Object> {
+    // Create local JS object.
+    let target = v8::Object::new(scope);
+
+    set_function_to(scope, target, "previewEntries", preview_entries);
+    set_function_to(scope, target, "getAsyncContext", get_async_context);
+    set_function_to(scope, target, "setAsyncContext", set_async_context);
+    set_function_to(scope, target, "getSourceMap", get_source_map);
+    set_function_to(scope, target, "getProxyDetails", get_proxy_details);
+
+    // Return v8 global handle.
+    v8::Global::new(scope, target)
+}
+
+/// Returns the internal entries of a Map or Set object as a flat array.
+///
+/// Reading the entries through V8 (instead of `Symbol.iterator`) makes sure
+/// that user-land overrides of the iteration protocol are never invoked.
+fn preview_entries(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let value = args.get(0);
+
+    // Maps are flattened by V8 as `[key1, value1, key2, value2, ...]`.
+    if let Ok(map) = v8::Local::<v8::Map>::try_from(value) {
+        rv.set(map.as_array(scope).into());
+        return;
+    }
+
+    if let Ok(set) = v8::Local::<v8::Set>::try_from(value) {
+        rv.set(set.as_array(scope).into());
+        return;
+    }
+
+    let err = anyhow!("The provided object is not a Map or a Set.");
+    throw_exception(scope, &err);
+}
+
+/// Returns the value of the current async context.
+///
+/// V8 captures this value whenever a promise reaction is created and restores
+/// it when the reaction runs, so it follows the code across `await` points.
+fn get_async_context(
+    scope: &mut v8::HandleScope,
+    _: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    rv.set(scope.get_continuation_preserved_embedder_data());
+}
+
+/// Sets the value of the current async context.
+fn set_async_context(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+) {
+    scope.set_continuation_preserved_embedder_data(args.get(0));
+}
+
+/// Returns the (inline) source map of a loaded ES module, as a JSON string.
+///
+/// Transpiled modules (e.g. TypeScript) embed their source map as a base64
+/// `data:` URL, which V8 exposes through the `sourceMappingURL` comment.
+fn get_source_map(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    let path = args.get(0).to_rust_string_lossy(scope);
+    let state_rc = JsRuntime::state(scope);
+
+    // Note: Stack traces may refer to local modules through `file://` URLs.
+    let module = {
+        let state = state_rc.borrow();
+        let module_map = &state.module_map;
+        let module = module_map
+            .get(&path)
+            .or_else(|| module_map.get(path.trim_start_matches("file://")));
+        module
+    };
+
+    let module = match module {
+        Some(module) => v8::Local::new(scope, module),
+        None => return,
+    };
+
+    let url = module
+        .get_unbound_module_script(scope)
+        .get_source_mapping_url(scope)
+        .to_rust_string_lossy(scope);
+
+    let source_map = url
+        .strip_prefix("data:application/json;base64,")
+        .and_then(|data| BASE64_STANDARD.decode(data).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).to_string());
+
+    if let Some(source_map) = source_map {
+        rv.set(v8::String::new(scope, &source_map).unwrap().into());
+    }
+}
+
+/// Returns the target and the handler of a Proxy object (as a `[target, handler]`
+/// array), without invoking any of its traps.
+fn get_proxy_details(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    if let Ok(proxy) = v8::Local::<v8::Proxy>::try_from(args.get(0)) {
+        let target = proxy.get_target(scope);
+        let handler = proxy.get_handler(scope);
+        rv.set(v8::Array::new_with_elements(scope, &[target, handler]).into());
+    }
+}
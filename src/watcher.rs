@@ -74,7 +74,7 @@ pub fn start(script: &str, watch_paths: Vec<String>) -> Result<()> {
     }
 
     // Check if the script exists in the file-system.
-    if let Err(e) = load_import(script, true) {
+    if let Err(e) = load_import(script, true, None) {
         bail!(e.to_string());
     }
 
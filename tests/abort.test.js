@@ -0,0 +1,29 @@
+import test from 'test';
+import assert from 'assert';
+
+test('[ABORT] Abort listeners run once with the reason already set.', () => {
+  const controller = new AbortController();
+  const calls = [];
+
+  controller.signal.onabort = () => calls.push('onabort');
+  controller.signal.addEventListener('abort', (event) => {
+    assert.true(event.target.aborted);
+    calls.push(event.target.reason.name);
+  });
+
+  controller.abort();
+  controller.abort();
+
+  assert.equal(calls.join(), 'onabort,AbortError');
+});
+
+test('[ABORT] Listeners added after the abort are never called.', () => {
+  const controller = new AbortController();
+  let called = false;
+
+  controller.abort();
+  controller.signal.addEventListener('abort', () => (called = true));
+  controller.signal.dispatchEvent('abort');
+
+  assert.false(called);
+});
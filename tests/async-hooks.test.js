@@ -0,0 +1,46 @@
+import test from 'test';
+import assert from 'assert';
+import { AsyncLocalStorage } from 'async_hooks';
+
+test('[ASYNC-HOOKS] Stores are visible inside timers.', async () => {
+  const storage = new AsyncLocalStorage();
+
+  const store = await storage.run({ id: 1 }, () => {
+    return new Promise((resolve) => {
+      setTimeout(() => resolve(storage.getStore()), 10);
+    });
+  });
+
+  assert.objectEqual(store, { id: 1 });
+  assert.equal(storage.getStore(), undefined);
+});
+
+test('[ASYNC-HOOKS] Stores are visible inside microtasks.', async () => {
+  const storage = new AsyncLocalStorage();
+
+  const stores = await storage.run('outer', async () => {
+    const queued = new Promise((resolve) => {
+      queueMicrotask(() => resolve(storage.getStore()));
+    });
+    await Promise.resolve();
+    return [await queued, storage.getStore()];
+  });
+
+  assert.objectEqual(stores, ['outer', 'outer']);
+});
+
+test('[ASYNC-HOOKS] Nested and unrelated stores are kept apart.', () => {
+  const first = new AsyncLocalStorage();
+  const second = new AsyncLocalStorage();
+
+  first.run('a', () => {
+    second.run('b', () => {
+      assert.equal(first.getStore(), 'a');
+      assert.equal(second.getStore(), 'b');
+      first.run('c', () => assert.equal(first.getStore(), 'c'));
+      first.exit(() => assert.equal(first.getStore(), undefined));
+    });
+    assert.equal(first.getStore(), 'a');
+    assert.equal(second.getStore(), undefined);
+  });
+});
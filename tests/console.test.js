@@ -0,0 +1,531 @@
+import test from 'test';
+import assert from 'assert';
+import { inspect } from 'console';
+
+const options = { colors: false };
+
+test('[CONSOLE] Map entries should be inspected.', () => {
+  const map = new Map([
+    ['a', 1],
+    ['b', 2],
+  ]);
+  assert.equal(inspect(map, options), "Map(2) { 'a' => 1, 'b' => 2 }");
+});
+
+test('[CONSOLE] Set entries should be inspected.', () => {
+  const set = new Set([1, 2, 3]);
+  assert.equal(inspect(set, options), 'Set(3) { 1, 2, 3 }');
+});
+
+test('[CONSOLE] Map and Set entries should respect the iterable limit.', () => {
+  const map = new Map([
+    ['a', 1],
+    ['b', 2],
+    ['c', 3],
+  ]);
+  const set = new Set([1, 2, 3, 4, 5]);
+  const limited = { ...options, iterableLimit: 2 };
+
+  assert.equal(
+    inspect(map, limited),
+    "Map(3) { 'a' => 1, 'b' => 2, ... 1 more entry }"
+  );
+  assert.equal(inspect(set, limited), 'Set(5) { 1, 2, ... 3 more items }');
+});
+
+test('[CONSOLE] Set inspection should not invoke user iterators.', () => {
+  const set = new Set([1, 2]);
+  set[Symbol.iterator] = () => {
+    throw new Error('Iterator should not be called.');
+  };
+  assert.equal(inspect(set, options), 'Set(2) { 1, 2 }');
+});
+
+test('[CONSOLE] Special numbers should be printed literally.', () => {
+  assert.equal(inspect(-0, options), '-0');
+  assert.equal(inspect(NaN, options), 'NaN');
+  assert.equal(inspect(Infinity, options), 'Infinity');
+  assert.equal(inspect(-Infinity, options), '-Infinity');
+  assert.equal(inspect([0, -0], options), '[ 0, -0 ]');
+});
+
+test('[CONSOLE] Large numbers should support numeric separators.', () => {
+  const separated = { ...options, numericSeparator: true };
+  assert.equal(inspect(1234567, separated), '1_234_567');
+  assert.equal(inspect(-1234567.891, separated), '-1_234_567.891');
+  assert.equal(inspect(123n, separated), '123n');
+  assert.equal(inspect(1234567n, separated), '1_234_567n');
+  assert.equal(inspect(1234567, options), '1234567');
+});
+
+test('[CONSOLE] Circular references should be labeled.', () => {
+  const shared = { value: 1 };
+  const root = { shared, other: shared };
+  root.self = root;
+  root.child = { parent: root };
+
+  assert.equal(
+    inspect(root, options),
+    '<ref *1> {\n' +
+      '  shared: { value: 1 },\n' +
+      '  other: { value: 1 },\n' +
+      '  self: [Circular *1],\n' +
+      '  child: { parent: [Circular *1] }\n' +
+      '}'
+  );
+});
+
+test('[CONSOLE] Circular arrays should be labeled.', () => {
+  const list = [1, 2];
+  list.push(list);
+  assert.equal(inspect(list, options), '<ref *1> [ 1, 2, [Circular *1] ]');
+});
+
+test('[CONSOLE] Getters should not be invoked by default.', () => {
+  let calls = 0;
+  const object = {
+    get value() {
+      calls++;
+      return 42;
+    },
+    set value(_) {},
+    set other(_) {},
+  };
+
+  const output = inspect(object, options);
+
+  assert.equal(output, '{ value: [Getter/Setter], other: [Setter] }');
+  assert.equal(calls, 0);
+});
+
+test('[CONSOLE] Getters should be evaluated with the getters option.', () => {
+  let calls = 0;
+  const object = {
+    get value() {
+      calls++;
+      return 42;
+    },
+    get broken() {
+      throw new Error('Oops');
+    },
+  };
+
+  const output = inspect(object, { ...options, getters: true });
+
+  assert.equal(
+    output,
+    '{ value: [Getter: 42], broken: [Getter: <Inspection threw (Oops)>] }'
+  );
+  assert.equal(calls, 1);
+});
+
+test('[CONSOLE] Strings should be quoted and escaped inside objects.', () => {
+  const values = ['line 1\nline 2', "it's", `it's "quoted"`, 'tab\t\x1b'];
+  const expected = [
+    "'line 1\\nline 2'",
+    `"it's"`,
+    '`it\'s "quoted"`',
+    "'tab\\t\\x1B'",
+  ];
+
+  assert.equal(inspect(values, options), `[ ${expected.join(', ')} ]`);
+  assert.equal(inspect('top\nlevel', options), 'top\nlevel');
+});
+
+test('[CONSOLE] Strings with all quote types should be escaped.', () => {
+  const value = `'single' "double" \`back\``;
+  const expected = `[ '\\'single\\' "double" \`back\`' ]`;
+  assert.equal(inspect([value], options), expected);
+});
+
+test('[CONSOLE] Errors should display their properties and causes.', () => {
+  const root = new TypeError('connection refused');
+  const cause = new Error('socket closed', { cause: root });
+  const error = new Error('request failed', { cause });
+  error.code = 'E_REQUEST';
+
+  const output = inspect(error, options);
+  const lines = output.split('\n');
+
+  assert.true(output.startsWith('Error: request failed\n    at '));
+  assert.true(lines.includes(`  code: 'E_REQUEST',`));
+  assert.true(lines.includes('  [cause]: Error: socket closed'));
+  assert.true(lines.includes('    [cause]: TypeError: connection refused'));
+  assert.equal(lines.at(-1), '}');
+
+  const plain = new Error('plain');
+  assert.equal(inspect(plain, options), plain.stack);
+});
+
+test(
+  '[CONSOLE] Nested objects should wrap with consistent indentation.',
+  () => {
+    const value = {
+      id: 1,
+      user: {
+        name: 'dune',
+        roles: ['admin', 'maintainer', 'reviewer', 'contributor', 'triager'],
+        address: { city: 'Barcelona', country: 'Spain' },
+      },
+      tags: ['js'],
+    };
+
+    assert.equal(
+      inspect(value, options),
+      '{\n' +
+        '  id: 1,\n' +
+        '  user: {\n' +
+        "    name: 'dune',\n" +
+        "    roles: [ 'admin', 'maintainer', 'reviewer', 'contributor', 'triager' ],\n" +
+        "    address: { city: 'Barcelona', country: 'Spain' }\n" +
+        '  },\n' +
+        "  tags: [ 'js' ]\n" +
+        '}'
+    );
+  }
+);
+
+test('[CONSOLE] Dates should be inspected as ISO 8601 strings.', () => {
+  const date = new Date(Date.UTC(2024, 0, 2, 3, 4, 5));
+  assert.equal(inspect(date, options), '2024-01-02T03:04:05.000Z');
+  assert.equal(
+    inspect({ date }, options),
+    '{ date: 2024-01-02T03:04:05.000Z }'
+  );
+});
+
+test('[CONSOLE] Invalid dates should be inspected as Invalid Date.', () => {
+  assert.equal(inspect(new Date('not a date'), options), 'Invalid Date');
+});
+
+test('[CONSOLE] Functions should be inspected with their name.', () => {
+  function named() {}
+  named.answer = 42;
+
+  assert.equal(inspect(named, options), '[Function: named] { answer: 42 }');
+  assert.equal(inspect(() => {}, options), '[Function (anonymous)]');
+});
+
+test('[CONSOLE] Classes should be inspected with their heritage.', () => {
+  class Base {}
+  class Derived extends Base {}
+
+  assert.equal(inspect(Base, options), '[class Base]');
+  assert.equal(inspect(Derived, options), '[class Derived extends Base]');
+});
+
+test('[CONSOLE] Async and generator functions should be tagged.', () => {
+  async function load() {}
+  function* items() {}
+
+  assert.equal(inspect(load, options), '[AsyncFunction: load]');
+  assert.equal(inspect(items, options), '[GeneratorFunction: items]');
+  assert.equal(inspect({ load }, options), '{ load: [AsyncFunction: load] }');
+});
+
+test('[CONSOLE] Map keys that are objects should be inspected.', () => {
+  const map = new Map([[{ id: 1 }, 'first']]);
+  assert.equal(inspect(map, options), "Map(1) { { id: 1 } => 'first' }");
+});
+
+test('[CONSOLE] The sorted option should sort keys and entries.', () => {
+  const sorted = { ...options, sorted: true };
+  const map = new Map([
+    ['b', 2],
+    ['a', 1],
+  ]);
+
+  assert.equal(inspect({ c: 3, a: 1, b: 2 }, sorted), '{ a: 1, b: 2, c: 3 }');
+  assert.equal(inspect(map, sorted), "Map(2) { 'a' => 1, 'b' => 2 }");
+  assert.equal(inspect(new Set([3, 1, 2]), sorted), 'Set(3) { 1, 2, 3 }');
+});
+
+test('[CONSOLE] Long strings should be elided with a character count.', () => {
+  const text = 'a'.repeat(5000);
+  const truncated = `'${'a'.repeat(100)}'... 4900 more characters`;
+  const raised = { ...options, strAbbreviateSize: 5000 };
+
+  assert.equal(inspect({ text }, options), `{\n  text: ${truncated}\n}`);
+  assert.equal(inspect({ text }, raised), `{\n  text: '${text}'\n}`);
+});
+
+test('[CONSOLE] Deeply nested objects should be truncated.', () => {
+  let value = {};
+  for (let i = 0; i < 50000; i++) {
+    value = { next: value };
+  }
+
+  const output = inspect(value, options);
+  assert.true(output.length < 200);
+  assert.true(output.includes('[Object]'));
+  assert.equal(
+    inspect({ a: { b: [1] } }, { ...options, depth: 1 }),
+    '{ a: { b: [Array] } }'
+  );
+});
+
+test('[CONSOLE] Regular expressions should be inspected as literals.', () => {
+  assert.equal(inspect(/a+b/gi, options), '/a+b/gi');
+  assert.equal(inspect({ exp: /\d/ }, options), '{ exp: /\\d/ }');
+});
+
+test('[CONSOLE] Symbols should be inspected with their description.', () => {
+  assert.equal(inspect(Symbol('dune'), options), 'Symbol(dune)');
+  assert.equal(inspect([Symbol()], options), '[ Symbol() ]');
+});
+
+test(
+  '[CONSOLE] Boxed primitives should be distinguished from primitives.',
+  () => {
+    assert.equal(inspect(new Number(5), options), '[Number: 5]');
+    assert.equal(inspect(new String('dune'), options), "[String: 'dune']");
+    assert.equal(inspect(new Boolean(false), options), '[Boolean: false]');
+    assert.equal(inspect(5, options), '5');
+  }
+);
+
+test('[CONSOLE] Object keys should be quoted only when necessary.', () => {
+  const object = { foo: 1, 'a-b': 2, 123: 3, '': 4, $bar_1: 5 };
+  assert.equal(
+    inspect(object, options),
+    "{ '123': 3, foo: 1, 'a-b': 2, '': 4, $bar_1: 5 }"
+  );
+});
+
+test('[CONSOLE] The compact option controls how objects are combined.', () => {
+  const object = { a: { b: { c: { d: 1 } } }, e: [] };
+  const nested = { ...options, depth: 10 };
+
+  assert.equal(
+    inspect(object, nested),
+    '{\n  a: { b: { c: { d: 1 } } },\n  e: []\n}'
+  );
+  assert.equal(
+    inspect(object, { ...nested, compact: true }),
+    '{ a: { b: { c: { d: 1 } } }, e: [] }'
+  );
+  assert.equal(
+    inspect(object, { ...nested, compact: false }),
+    '{\n  a: {\n    b: {\n      c: {\n        d: 1\n      }\n    }\n  },\n' +
+      '  e: []\n}'
+  );
+  assert.equal(
+    inspect(object, { ...nested, compact: 1 }),
+    '{\n  a: {\n    b: {\n      c: { d: 1 }\n    }\n  },\n  e: []\n}'
+  );
+});
+
+test('[CONSOLE] The breakLength option controls when objects wrap.', () => {
+  const object = { name: 'dune', tags: ['js', 'rust'], stable: false };
+
+  assert.equal(
+    inspect(object, { ...options, breakLength: 20 }),
+    "{\n  name: 'dune',\n  tags: [ 'js', 'rust' ],\n  stable: false\n}"
+  );
+  assert.equal(
+    inspect(object, { ...options, breakLength: Infinity }),
+    "{ name: 'dune', tags: [ 'js', 'rust' ], stable: false }"
+  );
+});
+
+test(
+  '[CONSOLE] Colored output wraps at the same visible columns as plain output.',
+  () => {
+    const words = Array.from({ length: 30 }, (_, i) => `word${i}`);
+    const colored = inspect(words, { colors: true, breakLength: 40 });
+    const plain = inspect(words, { colors: false, breakLength: 40 });
+    const visible = colored.replace(/\u001b\[[0-9;]*m/g, '');
+
+    assert.true(colored.includes('\u001b['));
+    assert.equal(visible, plain);
+    for (const line of plain.split('\n')) {
+      assert.true(line.length <= 40);
+    }
+  }
+);
+
+test('[CONSOLE] Promises should be inspected without being awaited.', () => {
+  let subscribed = false;
+  const pending = new Promise(() => {});
+  pending.then = () => (subscribed = true);
+
+  assert.equal(
+    inspect([Promise.resolve(1), pending], options),
+    '[ Promise { 1 }, Promise { <pending> } ]'
+  );
+  assert.equal(
+    inspect({ all: [Promise.resolve({ a: [] })] }, { ...options, depth: 2 }),
+    '{ all: [ Promise { [Object] } ] }'
+  );
+  assert.false(subscribed);
+});
+
+test('[CONSOLE] Sparse arrays should show their holes as empty items.', () => {
+  const sparse = [1];
+  sparse[3] = 4;
+  const leading = [];
+  leading[1] = 'a';
+
+  assert.equal(inspect(sparse, options), '[ 1, <2 empty items>, 4 ]');
+  assert.equal(inspect(leading, options), "[ <1 empty item>, 'a' ]");
+  assert.equal(inspect(new Array(3), options), '[ <3 empty items> ]');
+});
+
+test(
+  '[CONSOLE] Array-like objects should be distinguished from arrays.',
+  () => {
+    const args = (function () {
+      return arguments;
+    })(1, 'a');
+
+    assert.equal(inspect(args, options), "[Arguments] [ 1, 'a' ]");
+    assert.equal(
+      inspect({ 0: 'a', 1: 'b', length: 2 }, options),
+      "{ '0': 'a', '1': 'b', length: 2 }"
+    );
+  }
+);
+
+test('[CONSOLE] Typed arrays should be inspected with their type.', () => {
+  assert.equal(
+    inspect(new Float32Array([1.5, -0, 3]), options),
+    'Float32Array(3) [ 1.5, -0, 3 ]'
+  );
+  assert.equal(
+    inspect(new BigInt64Array([1n, -2n]), options),
+    'BigInt64Array(2) [ 1n, -2n ]'
+  );
+  assert.equal(inspect(new Int16Array(0), options), 'Int16Array(0) []');
+});
+
+test('[CONSOLE] Data views should show their byte offset and length.', () => {
+  const view = new DataView(new ArrayBuffer(8), 2, 4);
+  assert.equal(
+    inspect(view, options),
+    'DataView { byteLength: 4, byteOffset: 2, ' +
+      'buffer: ArrayBuffer { byteLength: 8 } }'
+  );
+});
+
+test('[CONSOLE] Proxies should be revealed with the showProxy option.', () => {
+  const handler = { get: () => 'trapped' };
+  const proxy = new Proxy({ name: 'dune' }, handler);
+
+  assert.equal(inspect(proxy, options), "{ name: 'dune' }");
+  assert.equal(
+    inspect(proxy, { ...options, showProxy: true }),
+    "Proxy [ { name: 'dune' }, { get: [Function: get] } ]"
+  );
+});
+
+test('[CONSOLE] Every collection should be truncated at the limit.', () => {
+  const limited = { ...options, iterableLimit: 2 };
+
+  assert.equal(inspect([1, 2, 3, 4], limited), '[ 1, 2, ... 2 more items ]');
+  assert.equal(
+    inspect(new Uint8Array([1, 2, 3]), limited),
+    'Uint8Array(3) [ 1, 2, ... 1 more item ]'
+  );
+  assert.equal(
+    inspect(new Set(['a', 'b', 'c']), limited),
+    "Set(3) { 'a', 'b', ... 1 more item }"
+  );
+  const map = new Map([
+    [1, 'a'],
+    [2, 'b'],
+    [3, 'c'],
+    [4, 'd'],
+  ]);
+  assert.equal(
+    inspect(map, limited),
+    "Map(4) { 1 => 'a', 2 => 'b', ... 2 more entries }"
+  );
+  assert.equal(
+    inspect({ a: 1, b: 2, c: 3 }, limited),
+    '{ a: 1, b: 2, ... 1 more entry }'
+  );
+});
+
+test('[CONSOLE] Prototype getters should never be invoked.', () => {
+  const throwing = {
+    get() {
+      throw new Error('The getter was invoked.');
+    },
+  };
+
+  class Host {}
+  Object.defineProperty(Host.prototype, 'constructor', throwing);
+  Object.defineProperty(Host.prototype, 'state', throwing);
+  const host = Object.assign(new Host(), { id: 1 });
+
+  class HostMap extends Map {}
+  Object.defineProperty(HostMap.prototype, 'size', throwing);
+  const map = Object.assign(new HostMap([['a', 1]]), { extra: true });
+
+  class HostView extends DataView {}
+  Object.defineProperty(HostView.prototype, 'byteLength', throwing);
+  const view = new HostView(new ArrayBuffer(4));
+
+  class HostError extends Error {}
+  Object.defineProperty(HostError.prototype, 'name', throwing);
+  const error = new HostError('failed');
+
+  assert.equal(inspect(host, options), '{ id: 1 }');
+  assert.equal(
+    inspect({ host }, { ...options, depth: 0 }),
+    '{ host: [Object] }'
+  );
+  assert.equal(inspect(map, options), "Map(1) { 'a' => 1 }");
+  assert.true(inspect(view, options).includes('byteLength: 4'));
+  assert.equal(inspect(error, options), '[Getter]: failed');
+});
+
+test('[CONSOLE] Symbol keys should only be shown with showHidden.', () => {
+  const object = {
+    name: 'dune',
+    [Symbol('secret')]: 42,
+    [Symbol.for('app.id')]: 1,
+    [Symbol.iterator]: null,
+  };
+
+  assert.equal(inspect(object, options), "{ name: 'dune' }");
+  assert.equal(
+    inspect(object, { ...options, showHidden: true }),
+    "{\n  name: 'dune',\n  [Symbol(secret)]: 42,\n" +
+      '  [Symbol.for(app.id)]: 1,\n  [Symbol.iterator]: null\n}'
+  );
+});
+
+test('[CONSOLE] The global object should be inspected as a summary.', () => {
+  globalThis.inspectedValue = { nested: { deep: true } };
+
+  try {
+    const start = performance.now();
+    const output = inspect(globalThis, options);
+    const elapsed = performance.now() - start;
+
+    assert.true(output.startsWith('Object [global] {'));
+    assert.true(output.includes('inspectedValue: [Object]'));
+    assert.lessThan(1000, elapsed);
+    assert.lessThan(20_000, output.length);
+  } finally {
+    delete globalThis.inspectedValue;
+  }
+});
+
+test('[CONSOLE] Objects without a prototype should be labeled.', () => {
+  const object = Object.assign(Object.create(null), { name: 'dune' });
+
+  assert.equal(
+    inspect(object, options),
+    "[Object: null prototype] { name: 'dune' }"
+  );
+  assert.equal(
+    inspect(Object.create(null), options),
+    '[Object: null prototype] {}'
+  );
+  assert.equal(
+    inspect({ object }, { ...options, depth: 0 }),
+    '{ object: [Object: null prototype] }'
+  );
+});
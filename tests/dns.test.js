@@ -0,0 +1,24 @@
+import test from 'test';
+import assert from 'assert';
+import dns from 'dns';
+
+const options = { timeout: 5000 };
+
+test('[DNS] Resolves localhost A records.', options, async () => {
+  const records = await dns.resolve('localhost', 'A');
+  assert.true(records.includes('127.0.0.1'));
+});
+
+test('[DNS] Resolves localhost AAAA records.', options, async () => {
+  const records = await dns.resolve('localhost', 'AAAA');
+  assert.true(records.includes('::1'));
+});
+
+test('[DNS] Rejects unsupported record types.', options, async () => {
+  try {
+    await dns.resolve('localhost', 'SOA');
+    assert.true(false);
+  } catch (err) {
+    assert.true(err instanceof TypeError);
+  }
+});
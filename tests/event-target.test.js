@@ -0,0 +1,45 @@
+import test from 'test';
+import assert from 'assert';
+
+class Channel extends EventTarget {}
+
+// Both targets should share the exact same listener semantics.
+const targets = {
+  Channel: () => new Channel(),
+  AbortSignal: () => new AbortController().signal,
+};
+
+for (const [name, createTarget] of Object.entries(targets)) {
+  test(`[EVENT-TARGET] Once listeners run a single time (${name}).`, () => {
+    const target = createTarget();
+    let calls = 0;
+
+    target.addEventListener('ping', () => calls++, { once: true });
+    target.dispatchEvent(new Event('ping'));
+    target.dispatchEvent(new Event('ping'));
+
+    assert.equal(calls, 1);
+  });
+
+  test(`[EVENT-TARGET] Multiple listeners run in order (${name}).`, () => {
+    const target = createTarget();
+    const calls = [];
+
+    const first = () => calls.push('first');
+    const second = { handleEvent: () => calls.push('second') };
+
+    target.addEventListener('ping', first);
+    target.addEventListener('ping', first);
+    target.addEventListener('ping', second);
+    target.addEventListener('ping', (event) => {
+      assert.true(event.target === target);
+      calls.push('third');
+    });
+
+    target.dispatchEvent(new Event('ping'));
+    target.removeEventListener('ping', first);
+    target.dispatchEvent(new Event('ping'));
+
+    assert.equal(calls.join(), 'first,second,third,second,third');
+  });
+}
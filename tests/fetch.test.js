@@ -0,0 +1,238 @@
+import test from 'test';
+import http from 'http';
+import net from 'net';
+import assert from 'assert';
+import { EventEmitter } from 'events';
+
+// The string "Hello from a gzip-encoded body!" compressed with gzip.
+const GZIP_BODY = new Uint8Array([
+  31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 243, 72, 205, 201, 201, 87, 72, 43, 202, 207,
+  85, 72, 84, 72, 175, 202, 44, 208, 77, 205, 75, 206, 79, 73, 77, 81, 72, 202,
+  79, 169, 84, 4, 0, 38, 49, 18, 230, 31, 0, 0, 0,
+]);
+
+// The string "Hello from a deflate-encoded body!" compressed with zlib.
+const DEFLATE_BODY = new Uint8Array([
+  120, 156, 243, 72, 205, 201, 201, 87, 72, 43, 202, 207, 85, 72, 84, 72, 73,
+  77, 203, 73, 44, 73, 213, 77, 205, 75, 206, 79, 73, 77, 81, 72, 202, 79, 169,
+  84, 4, 0, 213, 190, 12, 45,
+]);
+
+// The string "Hello from a brotli-encoded body!" compressed with brotli.
+const BROTLI_BODY = new Uint8Array([
+  27, 32, 0, 248, 141, 148, 110, 222, 52, 183, 177, 117, 245, 61, 207, 213, 39,
+  8, 65, 213, 66, 87, 138, 244, 146, 63, 124, 131, 18, 173, 174, 141, 252, 93,
+  85, 2, 4,
+]);
+
+// Starts the server on a random free port (retrying the ones in use).
+//
+// Note: The port is claimed by the server itself, so it can't be taken by
+// someone else between picking and binding it.
+async function listen(server) {
+  for (;;) {
+    const port = 20000 + Math.floor(Math.random() * 40000);
+    try {
+      return await server.listen(port, '127.0.0.1');
+    } catch (e) {
+      if (e.code !== 'EADDRINUSE') throw e;
+    }
+  }
+}
+
+// Serves the given body with the given content-encoding.
+async function serveEncoded(encoding, body) {
+  const server = http.createServer(async (_, res) => {
+    res.setHeader('content-encoding', encoding);
+    await res.end(body);
+  });
+  const { port } = await listen(server);
+  return { server, url: `http://127.0.0.1:${port}/` };
+}
+
+test('[FETCH] Gzip-encoded response bodies are decompressed.', async () => {
+  let acceptEncoding;
+  const server = http.createServer(async (req, res) => {
+    acceptEncoding = req.headers['accept-encoding'];
+    res.setHeader('content-encoding', 'gzip');
+    await res.end(GZIP_BODY);
+  });
+
+  const { port } = await listen(server);
+
+  try {
+    const response = await fetch(`http://127.0.0.1:${port}/`);
+
+    assert.equal(acceptEncoding, 'gzip, deflate, br');
+    assert.equal(await response.text(), 'Hello from a gzip-encoded body!');
+    assert.equal(response.headers['content-length'], undefined);
+  } finally {
+    await server.close();
+  }
+});
+
+test('[FETCH] Deflate and brotli bodies are decompressed.', async () => {
+  const encodings = [
+    ['deflate', DEFLATE_BODY, 'Hello from a deflate-encoded body!'],
+    ['br', BROTLI_BODY, 'Hello from a brotli-encoded body!'],
+  ];
+
+  for (const [encoding, body, expected] of encodings) {
+    const { server, url } = await serveEncoded(encoding, body);
+    try {
+      const response = await fetch(url);
+      assert.equal(await response.text(), expected);
+    } finally {
+      await server.close();
+    }
+  }
+});
+
+test('[FETCH] Raw deflate bodies are decompressed.', async () => {
+  // Note: Some servers omit the zlib header and checksum.
+  const body = DEFLATE_BODY.slice(2, -4);
+  const { server, url } = await serveEncoded('deflate', body);
+  try {
+    const response = await fetch(url);
+    assert.equal(await response.text(), 'Hello from a deflate-encoded body!');
+  } finally {
+    await server.close();
+  }
+});
+
+test('[FETCH] Chunked encoded bodies are decompressed.', async () => {
+  const encodings = [
+    ['gzip', GZIP_BODY, 'Hello from a gzip-encoded body!'],
+    ['deflate', DEFLATE_BODY, 'Hello from a deflate-encoded body!'],
+  ];
+
+  for (const [encoding, body, expected] of encodings) {
+    const server = http.createServer(async (_, res) => {
+      res.setHeader('content-encoding', encoding);
+      // Note: Every byte is sent as a separate chunk.
+      for (let i = 0; i < body.length; i++) {
+        await res.write(body.subarray(i, i + 1));
+      }
+      await res.end();
+    });
+
+    const { port } = await listen(server);
+
+    try {
+      const response = await fetch(`http://127.0.0.1:${port}/`);
+      assert.equal(await response.text(), expected);
+    } finally {
+      await server.close();
+    }
+  }
+});
+
+test('[FETCH] Encoded and plain bodies have the same type.', async () => {
+  const server = http.createServer(async (req, res) => {
+    if (req.url === '/gzip') res.setHeader('content-encoding', 'gzip');
+    await res.end(req.url === '/gzip' ? GZIP_BODY : 'Hello!');
+  });
+
+  const { port } = await listen(server);
+
+  try {
+    const encoded = await fetch(`http://127.0.0.1:${port}/gzip`);
+    const plain = await fetch(`http://127.0.0.1:${port}/`);
+
+    assert.equal(encoded.body.constructor, plain.body.constructor);
+    assert.equal(await encoded.text(), 'Hello from a gzip-encoded body!');
+    assert.equal(await plain.text(), 'Hello!');
+  } finally {
+    await server.close();
+  }
+});
+
+test('[FETCH] Truncated encoded bodies are rejected.', async () => {
+  const encodings = [
+    ['gzip', GZIP_BODY],
+    ['deflate', DEFLATE_BODY],
+    ['br', BROTLI_BODY],
+  ];
+
+  for (const [encoding, body] of encodings) {
+    const { server, url } = await serveEncoded(encoding, body.slice(0, -4));
+    try {
+      const response = await fetch(url);
+      let error;
+      try {
+        await response.text();
+      } catch (e) {
+        error = e;
+      }
+      assert.true(error instanceof Error);
+    } finally {
+      await server.close();
+    }
+  }
+});
+
+test('[FETCH] Sequential requests reuse the same connection.', async () => {
+  const sockets = new Set();
+  const server = http.createServer(async (req, res) => {
+    sockets.add(res.socket);
+    await res.end(req.url);
+  });
+
+  const { port } = await listen(server);
+
+  try {
+    for (let i = 0; i < 5; i++) {
+      const response = await fetch(`http://127.0.0.1:${port}/${i}`, {
+        keepAlive: true,
+      });
+      assert.equal(await response.text(), `/${i}`);
+    }
+
+    assert.lessThan(5, sockets.size);
+  } finally {
+    await server.close();
+  }
+});
+
+test('[FETCH] Responses without framing are never pooled.', async () => {
+  let connections = 0;
+  const server = net.createServer(async (socket) => {
+    connections++;
+    await socket.read();
+    await socket.write('HTTP/1.0 200 OK\r\n\r\n');
+    await socket.end();
+  });
+
+  const { port } = await listen(server);
+
+  try {
+    for (let i = 0; i < 2; i++) {
+      const response = await fetch(`http://127.0.0.1:${port}/`, {
+        keepAlive: true,
+      });
+      await response.text();
+    }
+
+    assert.equal(connections, 2);
+  } finally {
+    await server.close();
+  }
+});
+
+test('[FETCH] Idle pooled connections are dropped on errors.', () => {
+  const socket = Object.assign(new EventEmitter(), {
+    remotePort: 80,
+    destroyed: false,
+    setTimeout() {},
+    destroy() {
+      this.destroyed = true;
+    },
+  });
+
+  const pool = new http.ConnectionPool();
+  pool.release('localhost', 80, socket);
+  socket.emit('error', new Error('Connection reset by peer'));
+
+  assert.true(socket.destroyed);
+  assert.equal(pool.acquire('localhost', 80), undefined);
+});
@@ -0,0 +1,4 @@
+// Note: Used by the runtime's `execute_main_module` unit test.
+setTimeout(() => {
+  globalThis.timerResult = import.meta.main ? 'main' : 'not main';
+}, 10);
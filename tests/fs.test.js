@@ -1,7 +1,8 @@
-import test from 'test';
+import test, { TestRunner } from 'test';
 import assert from 'assert';
 import fs from 'fs';
 import { pipeline } from 'stream';
+import { activeResources } from 'util';
 
 test('[FILE-SYSTEM] Reads current test file into a Uint8Array.', async () => {
   const content = await fs.readFile(import.meta.url);
@@ -37,3 +38,277 @@ test('[FILE-SYSTEM] Crates a directory in current path.', async () => {
   await fs.rm(tempDir);
   assert.true(stat.isDirectory);
 });
+
+test(
+  '[FILE-SYSTEM] Reading a missing file exposes the ENOENT code.',
+  async () => {
+    try {
+      await fs.readFile(`./missing_${process.pid}.txt`);
+      assert.true(false);
+    } catch (err) {
+      assert.equal(err.code, 'ENOENT');
+    }
+  }
+);
+
+test(
+  '[FILE-SYSTEM] Permission errors expose the EACCES or EPERM code.',
+  { ignore: process.platform !== 'linux' },
+  async () => {
+    try {
+      await fs.mkdir('/sys/dune_test');
+      assert.true(false);
+    } catch (err) {
+      // The kernel reports EPERM instead of EACCES for privileged users.
+      assert.true(['EACCES', 'EPERM'].includes(err.code));
+    }
+  }
+);
+
+test(
+  '[FILE-SYSTEM] Changes file ownership, leaving null ids unchanged.',
+  { ignore: process.platform === 'win32' },
+  async () => {
+    const tempFile = `./tmp_chown_${process.pid}.txt`;
+    await fs.writeFile(tempFile, 'chown');
+    try {
+      await fs.chown(tempFile, null, null);
+      fs.chownSync(tempFile, null, null);
+      assert.throws(() => fs.chownSync(tempFile, -1, null));
+    } finally {
+      await fs.rm(tempFile);
+    }
+  }
+);
+
+test(
+  '[FILE-SYSTEM] Unprivileged ownership changes fail with EPERM.',
+  {
+    ignore: process.platform === 'win32' || process.getuid() === 0,
+    reason: 'Root can change the ownership of any file.',
+  },
+  async () => {
+    const tempFile = `./tmp_chown_root_${process.pid}.txt`;
+    await fs.writeFile(tempFile, 'chown');
+    try {
+      await fs.chown(tempFile, 0, 0);
+      assert.true(false);
+    } catch (err) {
+      assert.equal(err.code, 'EPERM');
+    } finally {
+      await fs.rm(tempFile);
+    }
+  }
+);
+
+test(
+  '[FILE-SYSTEM] Sets the access and modification times of a file.',
+  async () => {
+    const tempFile = `./tmp_utime_${process.pid}.txt`;
+    await fs.writeFile(tempFile, 'utime');
+    try {
+      const mtime = new Date('2020-01-02T03:04:05.000Z');
+      await fs.utime(tempFile, 1500000000, mtime);
+
+      // Note: Some file systems store timestamps with a 2 seconds resolution.
+      const stats = await fs.stat(tempFile);
+      assert.true(Math.abs(stats.mtimeMs - mtime.getTime()) <= 2000);
+      assert.true(Math.abs(stats.atimeMs - 1500000000 * 1000) <= 2000);
+
+      fs.utimeSync(tempFile, mtime, 0);
+      assert.true(fs.statSync(tempFile).mtimeMs <= 2000);
+    } finally {
+      await fs.rm(tempFile);
+    }
+  }
+);
+
+test('[FILE-SYSTEM] Exclusive file locks block other handles.', async () => {
+  const tempFile = `./tmp_lock_${process.pid}.txt`;
+  await fs.writeFile(tempFile, 'lock');
+
+  const first = new fs.File(tempFile, 'r');
+  const second = new fs.File(tempFile, 'r');
+  await first.open();
+  await second.open();
+
+  try {
+    await first.lock(true);
+    assert.false(second.tryLockSync(true));
+    assert.false(second.tryLockSync(false));
+
+    await first.unlock();
+    assert.true(second.tryLockSync(true));
+    second.unlockSync();
+  } finally {
+    await first.close();
+    await second.close();
+    await fs.rm(tempFile);
+  }
+});
+
+test(
+  '[FILE-SYSTEM] Leaked files are named by the resource sanitizer.',
+  async () => {
+    const tempFile = `./tmp_leak_${process.pid}.txt`;
+    const runner = new TestRunner();
+
+    let file;
+    const leakyTest = Object.assign(
+      async () => {
+        file = await fs.open(tempFile, 'w');
+      },
+      { sanitizeResources: true }
+    );
+
+    let message;
+    try {
+      await runner.runTest(leakyTest);
+    } catch (err) {
+      message = err.message;
+    }
+
+    const description = `A file (rid ${file.fd}) at "${tempFile}"`;
+    await file.close();
+    await fs.rm(tempFile);
+
+    assert.true(message.startsWith('Test leaked resources:'));
+    assert.true(message.includes(`${description} was opened during the test`));
+  }
+);
+
+test('[FILE-SYSTEM] Files are only tracked by sanitized tests.', async () => {
+  const tempFile = `./tmp_untracked_${process.pid}.txt`;
+  const file = await fs.open(tempFile, 'w');
+
+  try {
+    assert.false(activeResources().has(file));
+  } finally {
+    await file.close();
+    await fs.rm(tempFile);
+  }
+});
+
+test(
+  '[FILE-SYSTEM] Writing with createNew fails if the file exists.',
+  async () => {
+    const tempFile = `./tmp_create_new_${process.pid}.txt`;
+    await fs.writeFile(tempFile, 'first', { createNew: true });
+    try {
+      await fs.writeFile(tempFile, 'second', { createNew: true });
+      assert.true(false);
+    } catch (err) {
+      assert.equal(err.code, 'EEXIST');
+    } finally {
+      await fs.rm(tempFile);
+    }
+  }
+);
+
+test(
+  '[FILE-SYSTEM] Writing with create disabled fails for missing files.',
+  async () => {
+    try {
+      await fs.writeFile(`./missing_${process.pid}.txt`, 'data', {
+        create: false,
+      });
+      assert.true(false);
+    } catch (err) {
+      assert.equal(err.code, 'ENOENT');
+    }
+  }
+);
+
+test('[FILE-SYSTEM] Writing with append extends the file.', async () => {
+  const tempFile = `./tmp_append_${process.pid}.txt`;
+  const encoder = new TextEncoder();
+  await fs.writeFile(tempFile, encoder.encode('Hello'));
+  try {
+    await fs.writeFile(tempFile, encoder.encode(', Dune'), { append: true });
+    fs.writeFileSync(tempFile, '!', { append: true });
+    const content = await fs.readFile(tempFile, 'utf-8');
+    assert.equal(content, 'Hello, Dune!');
+
+    // Without append, the previous content is replaced.
+    await fs.writeFile(tempFile, 'Bye');
+    assert.equal(await fs.readFile(tempFile, 'utf-8'), 'Bye');
+  } finally {
+    await fs.rm(tempFile);
+  }
+});
+
+test('[FILE-SYSTEM] Writes a stream of chunks into a file.', async () => {
+  const tempFile = `./tmp_stream_${process.pid}.txt`;
+  async function* chunks() {
+    yield 'Welcome ';
+    yield new TextEncoder().encode('to Dune');
+  }
+  await fs.writeFile(tempFile, chunks());
+  try {
+    assert.equal(await fs.readFile(tempFile, 'utf-8'), 'Welcome to Dune');
+  } finally {
+    await fs.rm(tempFile);
+  }
+});
+
+test(
+  '[FILE-SYSTEM] Writing a file creates it with the requested mode.',
+  { ignore: process.platform === 'win32' },
+  async () => {
+    const tempFile = `./tmp_mode_${process.pid}.txt`;
+    await fs.writeFile(tempFile, 'mode', { mode: 0o600 });
+    try {
+      assert.equal((await fs.stat(tempFile)).mode & 0o777, 0o600);
+      // Note: Existing files keep their permissions.
+      fs.writeFileSync(tempFile, 'mode', { mode: 0o640 });
+      assert.equal(fs.statSync(tempFile).mode & 0o777, 0o600);
+    } finally {
+      await fs.rm(tempFile);
+    }
+  }
+);
+
+test('[FILE-SYSTEM] Writing a file can be aborted.', async () => {
+  const controller = new AbortController();
+  controller.abort();
+  try {
+    await fs.writeFile(`./tmp_abort_${process.pid}.txt`, 'data', {
+      signal: controller.signal,
+    });
+    assert.true(false);
+  } catch (err) {
+    assert.equal(err.name, 'AbortError');
+  }
+});
+
+test('[FILE-SYSTEM] Walks a directory tree recursively.', async () => {
+  const root = import.meta.resolve('./fixtures/walk');
+  const relative = ({ path }) => path.slice(root.length + 1);
+
+  const paths = [];
+  for await (const entry of fs.walk(root, { maxDepth: 2, exts: ['.js'] })) {
+    if (entry.isFile) paths.push(relative(entry));
+  }
+  assert.equal(paths.join(), 'a.js,nested/c.js');
+
+  const options = { includeDirs: false, match: ['**/*.js'], skip: [/deeper/] };
+  const files = [...fs.walkSync(root, options)].map(relative);
+  assert.equal(files.join(), 'a.js,nested/c.js');
+
+  const dirs = [...fs.walkSync(root, { includeFiles: false })].map(relative);
+  assert.equal(dirs.join(), ',nested,nested/deeper');
+});
+
+test('[FILE-SYSTEM] Walking stops at symbolic link loops.', async () => {
+  // Note: The `a/loop` link points back to the root directory.
+  const root = import.meta.resolve('./fixtures/walk-loop');
+  const relative = ({ path }) => path.slice(root.length + 1);
+  const options = { followSymlinks: true };
+
+  const paths = [];
+  for await (const entry of fs.walk(root, options)) paths.push(relative(entry));
+  assert.equal(paths.join(), ',a,a/file.js,a/loop');
+
+  const entries = [...fs.walkSync(root, options)].map(relative);
+  assert.equal(entries.join(), ',a,a/file.js,a/loop');
+});
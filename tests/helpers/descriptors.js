@@ -0,0 +1,9 @@
+// A test module exporting test descriptors (used by the test-runner tests).
+const square = (n) => () => {
+  if (n * n !== n ** 2) throw new Error(`Failed to square ${n}.`);
+};
+
+export default [1, 2, 3].map((n) => ({
+  name: `[DESCRIPTORS] Squaring ${n} works.`,
+  fn: square(n),
+}));
@@ -0,0 +1,10 @@
+// A test module registering parameterized tests (used by the test-runner tests).
+import test from 'test';
+
+test.each([
+  [1, 1, 2],
+  [2, 3, 5],
+  [4, 4, 9],
+])('[EACH] %i + %i equals $2', ([a, b, sum]) => {
+  if (a + b !== sum) throw new Error(`Unexpected sum: ${a + b}.`);
+});
@@ -0,0 +1,8 @@
+// A test module that also has a default export (used by the test-runner tests).
+import test from 'test';
+
+test('[EXPORTS] Tests registered next to a default export run.', () => {});
+
+export default function () {
+  throw new Error('The default export should never be called.');
+}
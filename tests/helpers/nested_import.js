@@ -0,0 +1,7 @@
+// A test module running a nested import (used by the test-runner tests).
+import test, { TestRunner } from 'test';
+
+const inner = new TestRunner();
+await inner.importTests(import.meta.resolve('./descriptors.js'));
+
+test('[NESTED] Tests registered after a nested import run.', () => {});
@@ -0,0 +1,4 @@
+// Registers a test to the provided runner (used by the test-runner tests).
+export default function register(runner, description) {
+  runner.test(`[SUITE-A] ${description}`, () => {});
+}
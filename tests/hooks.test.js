@@ -0,0 +1,20 @@
+import test, { beforeAll, beforeEach, afterEach } from 'test';
+import assert from 'assert';
+
+const calls = [];
+
+beforeAll(() => calls.push('beforeAll'));
+beforeEach(() => calls.push('beforeEach'));
+afterEach(() => calls.push('afterEach'));
+
+test('[HOOKS] Hooks should run before the first test.', () => {
+  calls.push('first');
+  assert.equal(calls[0], 'beforeAll');
+  assert.equal(calls.slice(-2).join(), 'beforeEach,first');
+});
+
+test('[HOOKS] Hooks should run in order across tests.', () => {
+  calls.push('second');
+  const expected = 'beforeEach,first,afterEach,beforeEach,second';
+  assert.equal(calls.slice(-5).join(), expected);
+});
@@ -1,7 +1,7 @@
 import test from 'test';
 import assert from 'assert';
 import _ from 'https://cdn.skypack.dev/lodash';
-import data from './fixtures/data.json';
+import data from './fixtures/data.json' with { type: 'json' };
 import { num } from './helpers/function';
 
 const options = { timeout: 5000 };
@@ -27,6 +27,33 @@ test('[IMPORTS] JSON imports work.', options, () => {
   assert.equal(data?.color, 'Red');
 });
 
+test(
+  '[IMPORTS] JSON imports require the type attribute.',
+  options,
+  async () => {
+    try {
+      await import('./fixtures/data.json');
+      assert.true(false);
+    } catch (err) {
+      assert.true(err.message.includes('type: "json"'));
+    }
+  }
+);
+
+test(
+  '[IMPORTS] JSON imports with a query string require the type attribute.',
+  options,
+  async () => {
+    // Note: The attribute is checked before the module gets downloaded.
+    try {
+      await import('https://example.com/data.json?v=1#fragment');
+      assert.true(false);
+    } catch (err) {
+      assert.true(err.message.includes('type: "json"'));
+    }
+  }
+);
+
 // TODO: Figure out why this test behaves strangely. 🤔
 test('[IMPORTS] WASM imports work.', { ignore: true }, async () => {
   const { default: calc } = await import('./helpers/calc.wasm');
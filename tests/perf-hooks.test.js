@@ -0,0 +1,38 @@
+import test from 'test';
+import assert from 'assert';
+import { performance } from 'perf_hooks';
+
+test('[PERF-HOOKS] Measures the time between two marks.', () => {
+  const start = performance.mark('perf:start', { startTime: 10 });
+  performance.mark('perf:end', { startTime: 25, detail: 'done' });
+
+  const measure = performance.measure('perf:measure', 'perf:start', 'perf:end');
+  assert.equal(start.entryType, 'mark');
+  assert.equal(measure.startTime, 10);
+  assert.equal(measure.duration, 15);
+
+  const [end] = performance.getEntriesByName('perf:end');
+  assert.equal(end.detail, 'done');
+
+  assert.throws(() => performance.measure('perf:missing', 'perf:unknown'));
+
+  performance.clearMarks();
+  performance.clearMeasures();
+});
+
+test('[PERF-HOOKS] Clearing marks by name removes only those marks.', () => {
+  performance.mark('perf:first');
+  performance.mark('perf:second');
+  performance.mark('perf:third');
+  performance.measure('perf:second');
+
+  performance.clearMarks('perf:second');
+
+  const marks = performance.getEntriesByType('mark').map(({ name }) => name);
+  assert.equal(marks.join(), 'perf:first,perf:third');
+  assert.equal(performance.getEntriesByType('measure').length, 1);
+
+  performance.clearMarks();
+  performance.clearMeasures();
+  assert.equal(performance.getEntries().length, 0);
+});
@@ -0,0 +1,52 @@
+import test from 'test';
+import assert from 'assert';
+import { pipeline } from 'stream';
+
+test(
+  '[PROCESS] Catch handlers attached in the same turn prevent rejections.',
+  async () => {
+    let reported = false;
+    const listener = () => (reported = true);
+    process.on('unhandledRejection', listener);
+
+    await new Promise((resolve) => {
+      setTimeout(async () => {
+        const promise = Promise.reject(new Error('Handled later.'));
+        await Promise.resolve();
+        promise.catch(() => {});
+        resolve();
+      }, 0);
+    });
+
+    // Give the event-loop a chance to report unhandled rejections.
+    await new Promise((resolve) => setTimeout(resolve, 10));
+    process.removeListener('unhandledRejection', listener);
+
+    assert.false(reported);
+  }
+);
+
+test(
+  '[PROCESS] Byte streams can be piped into the stdout stream.',
+  async () => {
+    const writable = process.stdout.writable;
+    const bytesWritten = writable.bytesWritten;
+
+    const source = async function* () {
+      yield new Uint8Array([100, 117, 110, 101, 10]);
+    };
+
+    await pipeline(source(), writable);
+    assert.equal(writable.bytesWritten - bytesWritten, 5);
+  }
+);
+
+test('[PROCESS] The user CPU time should increase while spinning.', () => {
+  const start = process.cpuUsage();
+  const until = Date.now() + 50;
+  while (Date.now() < until);
+
+  const usage = process.cpuUsage(start);
+  assert.greaterThan(0, usage.user);
+  assert.greaterThanOrEqual(0, usage.system);
+});
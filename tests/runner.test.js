@@ -0,0 +1,563 @@
+import test, { TestRunner, benchReport, shuffle, toJUnit } from 'test';
+import assert from 'assert';
+import { performance } from 'perf_hooks';
+import { pendingOps, trackBindingOps } from 'util';
+import registerSuiteA from './helpers/suite_a.js';
+import registerSuiteB from './helpers/suite_b.js';
+
+const items = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+
+test('[RUNNER] Shuffling with the same seed produces the same order.', () => {
+  assert.equal(shuffle(items, 42).join(), shuffle(items, 42).join());
+  assert.equal(shuffle(items, 42).length, items.length);
+});
+
+test(
+  '[RUNNER] Shuffling with different seeds can produce other orders.',
+  () => {
+    const orders = new Set();
+    for (let seed = 0; seed < 10; seed++) {
+      orders.add(shuffle(items, seed).join());
+    }
+    assert.greaterThan(1, orders.size);
+  }
+);
+
+test(
+  '[RUNNER] Tests fail when the assertion count does not match.',
+  async () => {
+    const runner = new TestRunner();
+    const testFn = Object.assign(() => assert.true(true), { assertions: 2 });
+
+    let message;
+    try {
+      await runner.runTest(testFn);
+    } catch (err) {
+      message = err.message;
+    }
+
+    assert.equal(message, 'Expected 2 assertions to run, but 1 did.');
+  }
+);
+
+test('[RUNNER] Tests pass when the assertion count matches.', async () => {
+  const runner = new TestRunner();
+  const testFn = Object.assign(
+    async () => {
+      assert.true(true);
+      await Promise.resolve();
+      assert.equal(1, 1);
+    },
+    { assertions: 2 }
+  );
+  await runner.runTest(testFn);
+});
+
+test(
+  '[RUNNER] Async generator test bodies are driven to completion.',
+  async () => {
+    const runner = new TestRunner();
+    let steps = 0;
+
+    await runner.runTest(async function* () {
+      for (let i = 0; i < 3; i++) {
+        yield await Promise.resolve(i);
+        steps++;
+      }
+    });
+
+    assert.equal(steps, 3);
+  }
+);
+
+test(
+  '[RUNNER] Tests are tagged with the file they were registered from.',
+  () => {
+    const runner = new TestRunner();
+    registerSuiteA(runner, 'first');
+
+    const [[, testFn]] = runner.collect();
+    assert.equal(testFn.file, import.meta.resolve('./helpers/suite_a.js'));
+  }
+);
+
+test('[RUNNER] Tests can be filtered by file and are grouped by file.', () => {
+  const runner = new TestRunner();
+  registerSuiteA(runner, 'first');
+  registerSuiteB(runner, 'first');
+  registerSuiteA(runner, 'second');
+
+  const grouped = runner.collect().map(([description]) => description);
+  const expected = '[SUITE-A] first,[SUITE-A] second,[SUITE-B] first';
+  assert.equal(grouped.join(), expected);
+
+  runner.fileFilter = import.meta.resolve('./helpers/suite_b.js');
+
+  const filtered = runner.collect().map(([description]) => description);
+  assert.equal(filtered.join(), '[SUITE-B] first');
+});
+
+test('[RUNNER] Independent tests can run concurrently.', async () => {
+  const runner = new TestRunner();
+  runner.concurrency = 4;
+
+  const sleep = () => new Promise((resolve) => setTimeout(resolve, 200));
+  for (let i = 0; i < 4; i++) {
+    runner.test(`[SLEEPY] ${i}`, sleep);
+  }
+
+  const startTime = performance.now();
+  await runner.runTests(runner.collect());
+  const elapsedTime = performance.now() - startTime;
+
+  assert.equal(runner.counters.ok, 4);
+  assert.lessThan(600, elapsedTime);
+});
+
+test('[RUNNER] Tests opting out of parallelism run serially.', async () => {
+  const runner = new TestRunner();
+  runner.concurrency = 4;
+
+  let running = 0;
+  let maxRunning = 0;
+
+  const track = async () => {
+    maxRunning = Math.max(maxRunning, ++running);
+    await new Promise((resolve) => setTimeout(resolve, 10));
+    running--;
+  };
+
+  for (let i = 0; i < 3; i++) {
+    runner.test(`[SERIAL] ${i}`, Object.assign(track, { parallel: false }));
+  }
+  await runner.runTests(runner.collect());
+
+  assert.equal(maxRunning, 1);
+});
+
+test(
+  '[RUNNER] Deferred console output is attributed to its originating test.',
+  async () => {
+    const runner = new TestRunner();
+    const first = { name: 'first' };
+    const second = { name: 'second' };
+
+    const deferredLog = (message, delay) => () =>
+      new Promise((resolve) => {
+        setTimeout(() => {
+          console.log(message);
+          resolve();
+        }, delay);
+      });
+
+    await Promise.all([
+      runner.runTest(deferredLog('from first', 20), first),
+      runner.runTest(deferredLog('from second', 10), second),
+    ]);
+
+    assert.equal(first.output.length, 1);
+    assert.equal(first.output[0].output, 'from first\n');
+    assert.equal(second.output.length, 1);
+    assert.equal(second.output[0].output, 'from second\n');
+  }
+);
+
+test(
+  '[RUNNER] Only registered tests run, default exports are ignored.',
+  async () => {
+    const runner = new TestRunner();
+    await runner.importTests(import.meta.resolve('./helpers/exports_value.js'));
+
+    const tests = runner.collect();
+    await runner.runTests(tests);
+
+    assert.equal(tests.length, 1);
+    assert.equal(runner.counters.ok, 1);
+    assert.equal(runner.counters.failed, 0);
+  }
+);
+
+test(
+  '[RUNNER] Tests default-exported as descriptors are registered.',
+  async () => {
+    const runner = new TestRunner();
+    await runner.importTests(import.meta.resolve('./helpers/descriptors.js'));
+    await runner.runTests(runner.collect());
+
+    assert.equal(runner.counters.ok, 3);
+    runner.results.forEach(({ description }, i) => {
+      assert.equal(description, `[DESCRIPTORS] Squaring ${i + 1} works.`);
+    });
+  }
+);
+
+test(
+  '[RUNNER] Tests registered after a nested import stay in their runner.',
+  async () => {
+    const runner = new TestRunner();
+    await runner.importTests(import.meta.resolve('./helpers/nested_import.js'));
+
+    const descriptions = runner.collect().map(([description]) => description);
+    assert.equal(
+      descriptions.join(),
+      '[NESTED] Tests registered after a nested import run.'
+    );
+  }
+);
+
+test(
+  '[RUNNER] Test durations are recorded and slow tests are marked.',
+  async (context) => {
+    const runner = new TestRunner();
+    runner.slowThreshold = 20;
+
+    const sleep = () => new Promise((resolve) => setTimeout(resolve, 30));
+    runner.test('[SLOW] sleeping', sleep);
+    await runner.runTests(runner.collect());
+
+    const [result] = runner.results;
+    assert.equal(result.description, '[SLOW] sleeping');
+    assert.greaterThanOrEqual(20, result.duration);
+    assert.true(result.slow);
+
+    const report = context.output.map(({ output }) => output).join('');
+    assert.true(report.includes('(slow)'));
+  }
+);
+
+test(
+  '[RUNNER] Fail-fast cancels the tests after the first failure.',
+  async () => {
+    const runner = new TestRunner();
+    runner.failFast = true;
+
+    runner.test('[FAIL-FAST] first', () => assert.true(false));
+    runner.test('[FAIL-FAST] second', () => {});
+    runner.test('[FAIL-FAST] third', () => {});
+    await runner.runTests(runner.collect());
+
+    const cancelled = runner.results
+      .filter((result) => result.cancelled)
+      .map((result) => result.description);
+
+    assert.equal(runner.counters.failed, 1);
+    assert.equal(runner.counters.cancelled, 2);
+    assert.equal(cancelled.join(), '[FAIL-FAST] second,[FAIL-FAST] third');
+  }
+);
+
+test(
+  '[RUNNER] Leaked async ops are reported with where they started.',
+  async () => {
+    const runner = new TestRunner();
+    runner.traceOps = true;
+
+    let timerId;
+    function scheduleTimer() {
+      timerId = setTimeout(() => {}, 1000);
+    }
+
+    const leakyTest = Object.assign(() => scheduleTimer(), {
+      sanitizeOps: true,
+    });
+
+    let message;
+    try {
+      await runner.runTest(leakyTest);
+    } catch (err) {
+      message = err.message;
+    }
+
+    clearTimeout(timerId);
+
+    assert.true(message.startsWith('Test leaked async ops:'));
+    assert.true(message.includes('A timer (setTimeout) with a 1000ms delay'));
+    assert.true(message.includes('at scheduleTimer'));
+    assert.true(message.includes('runner.test.js'));
+  }
+);
+
+test(
+  '[RUNNER] Deferred cleanups run in reverse order after the test.',
+  async () => {
+    const runner = new TestRunner();
+    const calls = [];
+
+    await runner.runTest((context) => {
+      context.defer(() => calls.push('first'));
+      context.defer(async () => calls.push('second'));
+      calls.push('body');
+      return () => calls.push('returned');
+    });
+
+    assert.equal(calls.join(), 'body,returned,second,first');
+  }
+);
+
+test(
+  '[RUNNER] Deferred cleanups run and report errors on failure.',
+  async () => {
+    const runner = new TestRunner();
+    let cleanedUp = false;
+
+    let error;
+    try {
+      await runner.runTest((context) => {
+        context.defer(() => (cleanedUp = true));
+        context.defer(() => {
+          throw new Error('Cleanup failed.');
+        });
+        throw new Error('Body failed.');
+      });
+    } catch (err) {
+      error = err;
+    }
+
+    assert.true(cleanedUp);
+    assert.true(error instanceof AggregateError);
+    assert.true(error.message.includes('Body failed.'));
+    assert.true(error.message.includes('Cleanup failed.'));
+  }
+);
+
+test(
+  '[RUNNER] Failure reports point to the line of the failed assertion.',
+  async (context) => {
+    const runner = new TestRunner();
+    const line = Number(new Error().stack.split('\n')[1].split(':').at(-2));
+
+    runner.test('[LOCATION] failing', () => {
+      assert.equal(1, 2); // This assertion is 3 lines below the error above.
+    });
+    await runner.runTests(runner.collect());
+
+    const [result] = runner.results;
+    assert.true(result.location.includes(`runner.test.js:${line + 3}:`));
+
+    const report = context.output.map(({ output }) => output).join('');
+    assert.true(report.includes(result.location));
+  }
+);
+
+// Note: The variable is global, so other tests mustn't collect meanwhile.
+test(
+  '[RUNNER] Tests can be filtered through the DUNE_TEST_FILTER variable.',
+  { parallel: false },
+  async () => {
+    const runner = new TestRunner();
+    runner.test('[ENV-FILTER] first', () => {});
+    runner.test('[ENV-FILTER] second', () => {});
+    runner.test('[OTHER] third', () => {});
+
+    process.env.DUNE_TEST_FILTER = '^\\[ENV-FILTER\\]';
+    try {
+      await runner.runTests(runner.collect());
+
+      const names = runner.results.map(({ description }) => description);
+      assert.equal(names.join(), '[ENV-FILTER] first,[ENV-FILTER] second');
+
+      // The programmatic filter takes precedence.
+      runner.filter = /third/;
+      assert.equal(runner.collect().length, 1);
+      assert.equal(runner.collect()[0][0], '[OTHER] third');
+    } finally {
+      delete process.env.DUNE_TEST_FILTER;
+    }
+  }
+);
+
+test(
+  '[RUNNER] Parallel tests are reported in declaration order.',
+  async (context) => {
+    const runner = new TestRunner();
+    runner.concurrency = 3;
+
+    const sleep = (delay) => () =>
+      new Promise((resolve) => {
+        setTimeout(() => {
+          console.log(`slept ${delay}`);
+          resolve();
+        }, delay);
+      });
+
+    runner.test('[ORDER] first', sleep(30));
+    runner.test('[ORDER] second', sleep(10));
+    runner.test('[ORDER] third', sleep(20));
+    await runner.runTests(runner.collect());
+
+    const names = runner.results.map(({ description }) => description);
+    assert.equal(names.join(), '[ORDER] first,[ORDER] second,[ORDER] third');
+
+    // Each test's output is emitted along with its report.
+    const report = context.output.map(({ output }) => output).join('');
+    const positions = ['first', 'second', 'third'].map((name) =>
+      report.indexOf(`[ORDER] ${name}`)
+    );
+    assert.true(positions[0] < positions[1] && positions[1] < positions[2]);
+  }
+);
+
+test('[RUNNER] Results can be reported as JUnit XML.', async () => {
+  const runner = new TestRunner();
+
+  runner.test('[JUNIT] passing', () => {});
+  runner.test('[JUNIT] failing <xml>', () => assert.equal(1, 2));
+  await runner.runTests(runner.collect());
+
+  const xml = toJUnit(runner.results);
+
+  // Every opened element should be closed (in order).
+  const tags = xml.replace(/^<\?xml.*\?>/, '').match(/<[^>]+>/g);
+  const open = [];
+  for (const tag of tags) {
+    const name = tag.match(/^<\/?([\w-]+)/)[1];
+    if (tag.startsWith('</')) assert.equal(open.pop(), name);
+    else if (!tag.endsWith('/>')) open.push(name);
+  }
+
+  assert.equal(open.length, 0);
+  assert.true(xml.includes('<testsuites name="dune" tests="2" failures="1"'));
+  assert.true(xml.includes('<testcase name="[JUNIT] passing"'));
+  assert.true(xml.includes('<testcase name="[JUNIT] failing &lt;xml&gt;"'));
+  assert.true(xml.includes('<failure message="'));
+});
+
+test('[RUNNER] Parameterized tests are registered once per case.', async () => {
+  const runner = new TestRunner();
+  await runner.importTests(import.meta.resolve('./helpers/each.js'));
+  await runner.runTests(runner.collect());
+
+  const names = runner.results.map(({ description }) => description);
+  assert.equal(
+    names.join(),
+    '[EACH] 1 + 1 equals 2,[EACH] 2 + 3 equals 5,[EACH] 4 + 4 equals 9'
+  );
+
+  const failed = runner.results.filter(({ ok }) => !ok);
+  assert.equal(failed.length, 1);
+  assert.equal(failed[0].description, '[EACH] 4 + 4 equals 9');
+  assert.equal(failed[0].error.message, 'Unexpected sum: 8.');
+});
+
+test(
+  '[RUNNER] Grouped benchmarks are compared to their baseline.',
+  async () => {
+    const runner = new TestRunner();
+    const noop = () => {};
+    const loop = () => {
+      let total = 0;
+      for (let i = 0; i < 1e5; i++) total += i;
+      return total;
+    };
+
+    Object.assign(noop, { group: 'loops', baseline: true, iterations: 20 });
+    Object.assign(loop, { group: 'loops', iterations: 20 });
+
+    runner.bench('[BENCH] noop', noop);
+    runner.bench('[BENCH] loop', loop);
+
+    const results = await runner.runBenchmarks();
+    const report = benchReport(results).replace(/\u001b\[[0-9;]*m/g, '');
+
+    assert.equal(results.length, 2);
+    assert.true(report.includes('group loops'));
+    assert.true(/\[BENCH\] noop .* \(baseline\)/.test(report));
+    assert.true(/\[BENCH\] loop .* \d+\.\d{2}x slower/.test(report));
+  }
+);
+
+test(
+  '[RUNNER] Tests ignored on the current platform are reported as skipped.',
+  async (context) => {
+    const runner = new TestRunner();
+    let ran = false;
+
+    const ignored = () => assert.true(false);
+    Object.assign(ignored, { ignore: [process.platform], reason: 'flaky' });
+
+    const other = () => (ran = true);
+    Object.assign(other, { ignore: 'not-a-platform' });
+
+    runner.test('[IGNORE] platform', ignored);
+    runner.test('[IGNORE] other platform', other);
+    await runner.runTests(runner.collect());
+
+    const [skipped, passed] = runner.results;
+    assert.true(skipped.ignored);
+    assert.equal(skipped.reason, 'flaky');
+    assert.false(Boolean(passed.ignored));
+    assert.true(passed.ok);
+    assert.true(ran);
+    assert.equal(runner.counters.ignored, 1);
+
+    const report = context.output.map(({ output }) => output).join('');
+    assert.true(report.includes('[IGNORE] platform'));
+    assert.true(report.includes('(flaky)'));
+
+    const xml = toJUnit(runner.results);
+    assert.true(xml.includes('skipped="1"'));
+    assert.true(xml.includes('<skipped message="flaky" />'));
+  }
+);
+
+test(
+  '[RUNNER] The suite summary counts every kind of outcome.',
+  async (context) => {
+    const runner = new TestRunner();
+    runner.filter = /\[SUMMARY\]/;
+
+    runner.test('[SUMMARY] passes', () => assert.true(true));
+    runner.test('[SUMMARY] also passes', () => assert.true(true));
+    runner.test('[SUMMARY] fails', () => assert.true(false));
+    runner.test('[SUMMARY] ignored', Object.assign(() => {}, { ignore: true }));
+    runner.test('filtered out', () => assert.true(false));
+
+    const report = await runner.runSuite();
+    assert.equal(report.passed, 2);
+    assert.equal(report.failed, 1);
+    assert.equal(report.ignored, 1);
+    assert.equal(report.filtered, 1);
+    assert.equal(report.cancelled, 0);
+    assert.equal(report.results.length, 4);
+    assert.equal(report.exitCode, 1);
+
+    const output = context.output.map(({ output }) => output).join('');
+    const summary = 'FAILED | 2 passed | 1 failed | 1 ignored | 1 filtered';
+    assert.true(output.replace(/\u001b\[[0-9;]*m/g, '').includes(summary));
+  }
+);
+
+test(
+  '[RUNNER] Leaked async ops of native bindings are reported.',
+  async () => {
+    const runner = new TestRunner();
+    const binding = trackBindingOps(
+      { wait: () => new Promise(() => {}), now: () => 1 },
+      'fake'
+    );
+
+    const leakyTest = Object.assign(() => void binding.wait('./file.txt'), {
+      sanitizeOps: true,
+    });
+
+    let message;
+    try {
+      await runner.runTest(leakyTest);
+    } catch (err) {
+      message = err.message;
+    }
+
+    assert.equal(binding.now(), 1);
+    assert.true(message.startsWith('Test leaked async ops:'));
+    assert.true(message.includes('An async op (fake.wait) on "./file.txt"'));
+  }
+);
+
+test('[RUNNER] Async ops are only recorded by sanitized tests.', () => {
+  const before = pendingOps().size;
+  const id = setTimeout(() => {}, 1000);
+  const after = pendingOps().size;
+  clearTimeout(id);
+
+  assert.equal(after, before);
+});
@@ -0,0 +1,116 @@
+import test from 'test';
+import fs from 'fs';
+import assert, {
+  assertInlineSnapshot,
+  assertSnapshot,
+  snapshots,
+} from 'assert';
+
+const tempDir = `./tmp_snapshots_${process.pid}`;
+const file = `${tempDir}/example.test.js`;
+const path = `${tempDir}/__snapshots__/example.test.js.snap`;
+
+test('[SNAPSHOT] Snapshots are stored and compared on later runs.', () => {
+  const value = { name: 'dune', tags: ['js', 'rust'] };
+
+  try {
+    // The first run stores the snapshot.
+    assertSnapshot({ name: 'project', file }, value);
+    const stored = fs.readFileSync(path, { encoding: 'utf-8' });
+    assert.true(stored.includes('project 1'));
+
+    // A later run with the same value matches.
+    assertSnapshot({ name: 'project', file }, value);
+
+    // A later run with a changed value fails with a diff.
+    let message;
+    try {
+      assertSnapshot({ name: 'project', file }, { ...value, name: 'deno' });
+    } catch (err) {
+      message = err.message;
+    }
+    assert.true(message.startsWith('Snapshot "project 1" does not match'));
+    assert.true(message.includes(`- { name: 'dune'`));
+    assert.true(message.includes(`+ { name: 'deno'`));
+
+    // Updating the snapshots accepts the changed value.
+    snapshots.update = true;
+    assertSnapshot({ name: 'project', file }, { ...value, name: 'deno' });
+    snapshots.update = false;
+    assertSnapshot({ name: 'project', file }, { ...value, name: 'deno' });
+  } finally {
+    snapshots.update = false;
+    fs.rmSync(tempDir, { recursive: true });
+  }
+});
+
+test('[SNAPSHOT] Multiple snapshots in a test are numbered.', (context) => {
+  const ctx = { ...context, file };
+
+  try {
+    assertSnapshot(ctx, 1);
+    assertSnapshot(ctx, 2);
+
+    const stored = JSON.parse(fs.readFileSync(path, { encoding: 'utf-8' }));
+    assert.equal(stored[`${context.name} 1`], '1');
+    assert.equal(stored[`${context.name} 2`], '2');
+  } finally {
+    fs.rmSync(tempDir, { recursive: true });
+  }
+});
+
+// Note: Updating snapshots is a global switch, so this test runs serially.
+test(
+  '[SNAPSHOT] Inline snapshots are written into the test file.',
+  { parallel: false },
+  async () => {
+    const dir = `${process.cwd()}/${tempDir}`;
+    const source = [
+      `import { assertInlineSnapshot } from 'assert';`,
+      ``,
+      `export function check(value) {`,
+      `  assertInlineSnapshot(value);`,
+      `  assertInlineSnapshot({ value, tags: ['a', 'b'] });`,
+      `  assertInlineSnapshot(value.length); assertInlineSnapshot(value[0]);`,
+      `}`,
+    ].join('\n');
+
+    try {
+      fs.mkdirSync(dir, { recursive: true });
+      fs.writeFileSync(`${dir}/inline.js`, source);
+      const { check } = await import(`${dir}/inline.js`);
+
+      // Snapshots are only filled in when updating.
+      assert.throws(() => check('dune'));
+
+      snapshots.update = true;
+      check('dune');
+      snapshots.update = false;
+
+      const filled = fs.readFileSync(`${dir}/inline.js`, { encoding: 'utf-8' });
+      assert.true(filled.includes('assertInlineSnapshot(value, `dune`);'));
+      assert.true(
+        filled.includes(
+          "assertInlineSnapshot({ value, tags: ['a', 'b'] }, " +
+            "`{ value: 'dune', tags: [ 'a', 'b' ] }`);"
+        )
+      );
+      assert.true(
+        filled.includes(
+          'assertInlineSnapshot(value.length, `4`); ' +
+            'assertInlineSnapshot(value[0], `d`);'
+        )
+      );
+
+      // A copy of the updated file compares against the inline snapshots.
+      fs.writeFileSync(`${dir}/inline_copy.js`, filled);
+      const copy = await import(`${dir}/inline_copy.js`);
+
+      copy.check('dune');
+      assert.throws(() => copy.check('deno'));
+    } finally {
+      snapshots.update = false;
+      fs.rmSync(tempDir, { recursive: true });
+    }
+  }
+);
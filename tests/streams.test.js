@@ -1,6 +1,6 @@
 import test from 'test';
 import assert from 'assert';
-import { pipeline } from 'stream';
+import { pipeline, lines } from 'stream';
 
 async function* inputStream$(sentence) {
   yield* sentence.split(' ');
@@ -21,3 +21,36 @@ test('[STREAMS] The stream pipeline completes.', async () => {
   await pipeline(inputStream$('Hello World!'), toUpperCase$, sink$);
   assert.equal(sentence, 'HELLOWORLD!');
 });
+
+test('[STREAMS] Test bodies can consume async generators.', async () => {
+  async function* numbers() {
+    for (let i = 1; i <= 4; i++) {
+      await new Promise((resolve) => setTimeout(resolve, 1));
+      yield i;
+    }
+  }
+
+  let sum = 0;
+  for await (const value of numbers()) {
+    sum += value;
+  }
+  assert.equal(sum, 10);
+});
+
+test('[STREAMS] Byte streams can be split into lines.', async () => {
+  const encoder = new TextEncoder();
+  async function* output() {
+    yield encoder.encode('first li');
+    yield encoder.encode('ne\r\nsecond line\n');
+    yield encoder.encode('third 🪐').slice(0, 8);
+    yield encoder.encode('🪐').slice(2);
+  }
+
+  const received = [];
+  for await (const line of lines(output())) {
+    received.push(line);
+  }
+
+  assert.equal(received.length, 3);
+  assert.equal(received.join('|'), 'first line|second line|third 🪐');
+});
@@ -0,0 +1,28 @@
+import test from 'test';
+import assert from 'assert';
+
+test(
+  '[CLONE] Errors should be cloned with their name, stack and cause.',
+  () => {
+    const error = new TypeError('Invalid input.', { cause: { code: 42 } });
+    const clone = structuredClone(error);
+
+    assert.false(clone === error);
+    assert.instanceOf(clone, TypeError);
+    assert.equal(clone.name, 'TypeError');
+    assert.equal(clone.message, 'Invalid input.');
+    assert.equal(clone.stack, error.stack);
+    assert.objectEqual(clone.cause, { code: 42 });
+    assert.false(clone.cause === error.cause);
+  }
+);
+
+test('[CLONE] Own properties of errors should be copied.', () => {
+  const error = new RangeError('Out of range.');
+  error.limit = 10;
+
+  const clone = structuredClone(error);
+
+  assert.instanceOf(clone, RangeError);
+  assert.equal(clone.limit, 10);
+});
@@ -60,3 +60,25 @@ test('[TIMERS] ClearImmediate should be supported.', options, () => {
   clearImmediate(id);
   assert.equal(data, 0);
 });
+
+test(
+  '[TIMERS] SetTimeout with a huge delay should fire immediately.',
+  options,
+  async () => {
+    await new Promise((resolve) => {
+      setTimeout(resolve, Math.pow(2, 40));
+    });
+    assert.true(true);
+  }
+);
+
+test(
+  '[TIMERS] SetTimeout with a negative delay should fire immediately.',
+  options,
+  async () => {
+    await new Promise((resolve) => {
+      setTimeout(resolve, -100);
+    });
+    assert.true(true);
+  }
+);